@@ -10,18 +10,38 @@ pub mod stc;
 
 pub const KEY_ARRAY: usize = 13;
 pub const POINTER_ARRAY: usize = KEY_ARRAY + 1;
+pub const MIN_KEYS: usize = KEY_ARRAY / 2 - 1;
 
-use std::fmt::Debug;
+pub use methods::index::Pos;
 
-use methods::iter::{IndexTreeIterator, IndexTreeKeys, IndexTreeSetIterator, IndexTreeValues};
-// use methods::iter::{IndexTreeIterator, IndexTreeKeys, IndexTreeValues};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering::{Equal, Greater, Less},
+    fmt::Debug,
+    iter::Rev,
+    ops::RangeBounds,
+};
+
+use methods::build::UnsortedKeyError;
+use methods::cursor::{Cursor, CursorMut};
+use methods::entry::{Entry, Handle, OccupiedEntry, OccupiedError, VacantEntry};
+use methods::iter::{
+    IndexTreeIntoIterator, IndexTreeIntoKeys, IndexTreeIntoValues, IndexTreeIterator,
+    IndexTreeKeys, IndexTreeSetIntoIterator, IndexTreeSetIterator, IndexTreeValues,
+    IndexTreeValuesMut,
+};
+use methods::range::{
+    index_range_from_bounds, IndexTreeRange, IndexTreeRangeMut, IndexTreeSetRange,
+};
+use methods::retain::ExtractIf;
+use methods::slice::{IndexTreeSetSlice, IndexTreeSlice};
 use stc::{
-    Node,
+    Item, Node,
     Output::{KeyExists, NewKeyPointer},
 };
 
 /// The 'Set' IndexTree data structure
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexTreeSet<K> {
     pub map: IndexTreeMap<K, ()>,
@@ -50,6 +70,53 @@ impl<K> IndexTreeSet<K> {
     }
 }
 
+impl<K> IndexTreeSet<K> {
+    /// Builds a set in linear time from keys that are already in strictly ascending order.
+    ///
+    /// The keys are not checked: if they are out of order or repeated, the set is still safe to use,
+    /// but lookups by key may return wrong results. Use [`IndexTreeSet::try_from_sorted_iter`] to check them.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let set = IndexTreeSet::from_sorted_iter(0..100);
+    /// assert_eq!(set.len(), 100);
+    /// assert!(set.contains_key(&42));
+    /// ```
+    pub fn from_sorted_iter<I: IntoIterator<Item = K>>(iter: I) -> IndexTreeSet<K> {
+        IndexTreeSet {
+            map: IndexTreeMap::from_sorted_iter(iter.into_iter().map(|key| (key, ()))),
+        }
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Builds a set in linear time from keys in strictly ascending order,
+    /// or returns an error naming the first key that is out of order or repeated.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let set = IndexTreeSet::try_from_sorted_iter([1, 2, 3]).unwrap();
+    /// assert_eq!(set.len(), 3);
+    ///
+    /// let error = IndexTreeSet::try_from_sorted_iter([1, 2, 2]).unwrap_err();
+    /// assert_eq!(error.index, 2);
+    /// ```
+    pub fn try_from_sorted_iter<I: IntoIterator<Item = K>>(
+        iter: I,
+    ) -> Result<IndexTreeSet<K>, UnsortedKeyError> {
+        let map = IndexTreeMap::try_from_sorted_pairs(iter.into_iter().map(|key| (key, ())))?;
+        Ok(IndexTreeSet { map })
+    }
+}
+
 impl<K> IndexTreeSet<K> {
     /// Clears the map, removing all elements.
     ///
@@ -72,6 +139,32 @@ impl<K> IndexTreeSet<K> {
     }
 }
 
+impl<K> IndexTreeSet<K> {
+    /// Retains only the keys specified by the predicate, visiting them in ascending order.
+    ///
+    /// Each rejected key is removed in place, so only the nodes that lose keys are rebalanced.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// for i in 0..8 {
+    ///     tree.insert(i);
+    /// }
+    /// tree.retain(|&k| k % 2 == 0);
+    /// assert!(tree.iter().eq([0, 2, 4, 6].iter()));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.map.retain(|key, _| f(key));
+    }
+}
+
 impl<K> IndexTreeSet<K> {
     /// Gets the number of items in the set
     ///
@@ -125,7 +218,11 @@ impl<K: Ord> IndexTreeSet<K> {
     /// assert_eq!(tree.contains_key(&1), true);
     /// assert_eq!(tree.contains_key(&2), false);
     /// ```
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.map.contains_key(key)
     }
 }
@@ -163,7 +260,11 @@ impl<K: Ord> IndexTreeSet<K> {
     /// assert_eq!(tree.get(&1), Some(&1));
     /// assert_eq!(tree.get(&2), None);
     /// ```
-    pub fn get(&self, key: &K) -> Option<&K> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.map.get_key_value(key).map(|(k, _)| k)
     }
 
@@ -199,10 +300,87 @@ impl<K: Ord> IndexTreeSet<K> {
     /// assert_eq!(tree.get_index_from_key(&1), Some(0));
     /// assert_eq!(tree.get_index_from_key(&2), None);
     /// ```
-    pub fn get_index_from_key(&self, key: &K) -> Option<usize> {
+    pub fn get_index_from_key<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.map.get_index_from_key(key)
     }
 
+    /// Returns the index of the key if it is in the set, otherwise the index where it would be inserted.
+    ///
+    /// The key may be any borrowed form of the set’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.rank(&20), Ok(1));
+    /// assert_eq!(tree.rank(&15), Err(1));
+    /// ```
+    pub fn rank<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.rank(key)
+    }
+
+    /// Returns the index of the first key in the set that is not less than the given key,
+    /// i.e. the number of keys that are less than the given key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.lower_bound_index(&10), 0);
+    /// assert_eq!(tree.lower_bound_index(&15), 1);
+    /// assert_eq!(tree.lower_bound_index(&25), 2);
+    /// ```
+    pub fn lower_bound_index<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.lower_bound_index(key)
+    }
+
+    /// Returns the index of the first key in the set that is greater than the given key,
+    /// i.e. the number of keys that are less than or equal to the given key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.upper_bound_index(&5), 0);
+    /// assert_eq!(tree.upper_bound_index(&10), 1);
+    /// assert_eq!(tree.upper_bound_index(&20), 2);
+    /// ```
+    pub fn upper_bound_index<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.upper_bound_index(key)
+    }
+
     /// Returns a reference to the key corresponding to the index.
     ///
     /// # Example
@@ -254,8 +432,8 @@ impl<K: Ord> IndexTreeSet<K> {
     }
 }
 
-impl<K: Ord + Clone> IndexTreeSet<K> {
-    /// Inserts a key into the set.  
+impl<K: Ord> IndexTreeSet<K> {
+    /// Returns the index and key of the greatest key in the set that is less than or equal to the given key.
     ///
     /// # Example
     ///
@@ -264,16 +442,21 @@ impl<K: Ord + Clone> IndexTreeSet<K> {
     /// use indextreemap::IndexTreeSet;
     ///
     /// let mut tree = IndexTreeSet::new();
-    /// tree.insert(1);
-    /// assert!(!tree.is_empty());
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.floor(&15), Some((0, &10)));
+    /// assert_eq!(tree.floor(&20), Some((1, &20)));
+    /// assert_eq!(tree.floor(&5), None);
     /// ```
-    pub fn insert(&mut self, key: K) {
-        self.map.insert(key, ())
+    pub fn floor<Q>(&self, key: &Q) -> Option<(usize, &K)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.floor(key).map(|(index, key, _)| (index, key))
     }
-}
 
-impl<K> IndexTreeSet<K> {
-    /// Gets an iterator over the entries of the set, sorted by key.
+    /// Returns the index and key of the least key in the set that is greater than or equal to the given key.
     ///
     /// # Example
     ///
@@ -281,23 +464,22 @@ impl<K> IndexTreeSet<K> {
     /// ```rust
     /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut map = IndexTreeSet::new();
-    /// map.insert(10);
-    /// map.insert(1);
-    ///
-    /// let first_key = map.iter().next().unwrap();
-    /// assert_eq!(first_key, &1);
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.ceiling(&15), Some((1, &20)));
+    /// assert_eq!(tree.ceiling(&10), Some((0, &10)));
+    /// assert_eq!(tree.ceiling(&25), None);
     /// ```
-    pub fn iter(&self) -> IndexTreeSetIterator<K> {
-        IndexTreeSetIterator {
-            tree: self,
-            index: 0,
-        }
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(usize, &K)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.ceiling(key).map(|(index, key, _)| (index, key))
     }
-}
 
-impl<K: Ord + Clone> IndexTreeSet<K> {
-    /// Removes an item from the map from its corresponding key, returning the key-value pair that was previously in the map.
+    /// Returns the index and key of the greatest key in the set that is strictly less than the given key.
     ///
     /// # Example
     ///
@@ -306,17 +488,22 @@ impl<K: Ord + Clone> IndexTreeSet<K> {
     /// use indextreemap::IndexTreeSet;
     ///
     /// let mut tree = IndexTreeSet::new();
-    /// tree.insert(1);
-    /// assert_eq!(tree.remove(&1), Some(1));
-    /// assert_eq!(tree.remove(&2), None);
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.predecessor(&20), Some((0, &10)));
+    /// assert_eq!(tree.predecessor(&10), None);
     /// ```
-    pub fn remove(&mut self, key: &K) -> Option<K> {
-        self.map.remove(key).map(|(k, _)| k)
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(usize, &K)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map
+            .predecessor(key)
+            .map(|(index, key, _)| (index, key))
     }
-}
 
-impl<K: Ord + Clone> IndexTreeSet<K> {
-    /// Removes an item from the map from its corresponding index, returning the key-value pair that was previously in the map.
+    /// Returns the index and key of the least key in the set that is strictly greater than the given key.
     ///
     /// # Example
     ///
@@ -325,17 +512,23 @@ impl<K: Ord + Clone> IndexTreeSet<K> {
     /// use indextreemap::IndexTreeSet;
     ///
     /// let mut tree = IndexTreeSet::new();
-    /// tree.insert(1);
-    /// assert_eq!(tree.remove_from_index(0), Some(1));
-    /// assert_eq!(tree.remove_from_index(1), None);
+    /// tree.insert(10);
+    /// tree.insert(20);
+    /// assert_eq!(tree.successor(&10), Some((1, &20)));
+    /// assert_eq!(tree.successor(&20), None);
     /// ```
-    pub fn remove_from_index(&mut self, index: usize) -> Option<K> {
-        self.map.remove_from_index(index).map(|(k, _)| k)
+    pub fn successor<Q>(&self, key: &Q) -> Option<(usize, &K)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.successor(key).map(|(index, key, _)| (index, key))
     }
-}
 
-impl<K: Ord + Clone> IndexTreeSet<K> {
-    /// Splits the map into two at the given key. Returns everything after the given key, including the key.
+    /// Returns the index and key `offset` positions after the given key, or before it if `offset` is negative.
+    ///
+    /// The key does not need to be in the set: an offset of `1` gives the least key greater than it,
+    /// and an offset of `-1` gives the greatest key less than it. An offset of `0` gives the key itself, if present.
     ///
     /// # Example
     ///
@@ -343,25 +536,25 @@ impl<K: Ord + Clone> IndexTreeSet<K> {
     /// ```rust
     /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut a = IndexTreeSet::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// a.insert(13);
-    /// a.insert(17);
-    /// a.insert(41);
-    ///
-    /// let b = a.split_off(&13);
-    ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
+    /// let tree = IndexTreeSet::from([10, 20, 30, 40]);
+    /// assert_eq!(tree.nth_from_key(&20, 2), Some((3, &40)));
+    /// assert_eq!(tree.nth_from_key(&25, 1), Some((2, &30)));
+    /// assert_eq!(tree.nth_from_key(&25, -2), Some((0, &10)));
+    /// assert_eq!(tree.nth_from_key(&25, 0), None);
     /// ```
-    pub fn split_off(&mut self, key: &K) -> IndexTreeSet<K> {
-        IndexTreeSet {
-            map: self.map.split_off(key),
-        }
+    pub fn nth_from_key<Q>(&self, key: &Q, offset: isize) -> Option<(usize, &K)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map
+            .nth_from_key(key, offset)
+            .map(|(index, key, _)| (index, key))
     }
+}
 
-    /// Splits the map into two at the given index. Returns everything after the given key, including the key.
+impl<K: Ord> IndexTreeSet<K> {
+    /// Inserts a key into the set, returning whether the key was new.
     ///
     /// # Example
     ///
@@ -369,168 +562,1155 @@ impl<K: Ord + Clone> IndexTreeSet<K> {
     /// ```rust
     /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut a = IndexTreeSet::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// a.insert(3);
-    /// a.insert(17);
-    /// a.insert(41);
-    ///
-    /// let b = a.split_off_from_index(2);
-    ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
+    /// let mut tree = IndexTreeSet::new();
+    /// assert!(tree.insert(1));
+    /// assert!(!tree.insert(1));
+    /// assert!(!tree.is_empty());
     /// ```
-    pub fn split_off_from_index(&mut self, index: usize) -> IndexTreeSet<K> {
-        IndexTreeSet {
-            map: self.map.split_off_from_index(index),
-        }
+    pub fn insert(&mut self, key: K) -> bool {
+        self.insert_full(key).1
     }
-}
-
-/// The 'Map' IndexTree data structure
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct IndexTreeMap<K, V> {
-    pub root: Box<Node<K, V>>,
-    pub size: usize,
-}
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Makes a new, empty IndexTreeMap.
-    ///
-    /// Does not allocate anything on its own.
+    /// Inserts a key into the set, returning the index of the key and whether it was new.
     ///
-    /// ### Example
+    /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
-    ///
-    /// let mut map = IndexTreeMap::new();
-    ///
-    /// map.insert(1, "a".to_string());
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// ```    
-    pub fn new() -> IndexTreeMap<K, V> {
-        IndexTreeMap::default()
-    }
-}
-
-impl<K, V> Default for IndexTreeMap<K, V> {
-    fn default() -> Self {
-        IndexTreeMap {
-            root: Node::new(),
-            size: 0,
-        }
+    /// let mut tree = IndexTreeSet::new();
+    /// assert_eq!(tree.insert_full(3), (0, true));
+    /// assert_eq!(tree.insert_full(1), (0, true));
+    /// assert_eq!(tree.insert_full(3), (1, false));
+    /// ```
+    pub fn insert_full(&mut self, key: K) -> (usize, bool) {
+        let (index, old) = self.map.insert_full(key, ());
+        (index, old.is_none())
     }
 }
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Clears the map, removing all elements.
-    ///
-    /// Does not allocate anything on its own.
+impl<K> IndexTreeSet<K> {
+    /// Gets an iterator over the entries of the set, sorted by key.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut map = IndexTreeMap::new();
+    /// let mut map = IndexTreeSet::new();
+    /// map.insert(10);
+    /// map.insert(1);
     ///
-    /// map.insert(1, "a".to_string());
-    /// map.clear();
-    /// assert!(map.is_empty());
+    /// let first_key = map.iter().next().unwrap();
+    /// assert_eq!(first_key, &1);
     /// ```
-    pub fn clear(&mut self) {
-        self.root = Node::new();
-        self.size = 0
+    pub fn iter(&self) -> IndexTreeSetIterator<'_, K> {
+        IndexTreeSetIterator {
+            range: self.map.iter(),
+        }
     }
 }
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Gets the number of items in the map
+impl<K: Ord> IndexTreeSet<K> {
+    /// Constructs an iterator over a sub-range of keys in the set, in sorted order.
+    ///
+    /// The index of the first key in the range is held in the `start` field of the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut map = IndexTreeMap::new();
+    /// let mut set = IndexTreeSet::new();
+    /// set.insert(3);
+    /// set.insert(5);
+    /// set.insert(8);
     ///
-    /// map.insert(1, "a".to_string());
-    /// assert_eq!(map.len(), 1);
+    /// let range = set.range(4..);
+    /// assert_eq!(range.range.start, 1);
+    /// assert_eq!(range.collect::<Vec<_>>(), vec![&5, &8]);
     /// ```
-    pub fn len(&self) -> usize {
-        self.size
+    pub fn range<Q, R>(&self, range: R) -> IndexTreeSetRange<'_, K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        IndexTreeSetRange {
+            range: self.map.range(range),
+        }
     }
 
-    /// Returns true if the map contains no elements.
+    /// Returns the number of keys in the set that fall in the given range, without iterating over them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut map = IndexTreeMap::new();
-    /// assert!(map.is_empty());
-    /// map.insert(1, "a");
-    /// assert!(!map.is_empty());
+    /// let set = IndexTreeSet::from([3, 5, 8]);
+    /// assert_eq!(set.count_range(4..), 2);
+    /// assert_eq!(set.count_range(..=5), 2);
+    /// assert_eq!(set.count_range(6..8), 0);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.size == 0
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.map.count_range(range)
     }
 }
 
-impl<K: Ord, V> IndexTreeMap<K, V> {
-    /// Returns true if the map contains a value for the specified key.
+impl<K> IndexTreeSet<K> {
+    /// Constructs an iterator over the keys between two index positions, in sorted order.
     ///
-    /// The key may be any borrowed form of the map’s key type, but the
-    /// ordering on the borrowed form must match the ordering on the key type.
+    /// The end of the range is clamped to the length of the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.contains_key(&1), true);
-    /// assert_eq!(tree.contains_key(&2), false);
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = set.range_by_index(3..6).collect();
+    /// assert_eq!(keys, vec![&30, &40, &50]);
     /// ```
-    pub fn contains_key(&self, key: &K) -> bool {
-        self.root.get(key).is_some()
+    pub fn range_by_index<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeSetRange<'_, K> {
+        IndexTreeSetRange {
+            range: self.map.range_by_index(range),
+        }
     }
-}
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Returns true if the map contains an item in the index position.
+    /// Returns a borrowed view of the keys between two index positions.
+    ///
+    /// Indexes into the view are relative to the start of the view, and the view can be sliced further.
+    /// The end of the range is clamped to the length of the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
-    /// use indextreemap::IndexTreeMap;
+    /// use indextreemap::IndexTreeSet;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.contains_index(0), true);
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let page = set.slice(2..8);
+    /// assert_eq!(page.len(), 6);
+    /// assert_eq!(page.get(0), Some(&20));
+    /// assert_eq!(page.slice(4..).get(0), Some(&60));
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeSetSlice<'_, K> {
+        IndexTreeSetSlice {
+            slice: self.map.slice(range),
+        }
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Constructs an iterator over the keys of the set starting at the given key, or at the next greater key if it is absent.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..5 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = set.iter_from_key(&15).collect();
+    /// assert_eq!(keys, [&20, &30, &40]);
+    /// ```
+    pub fn iter_from_key<Q>(&self, key: &Q) -> IndexTreeSetRange<'_, K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        IndexTreeSetRange {
+            range: self.map.iter_from_key(key),
+        }
+    }
+
+    /// Constructs an iterator over the keys of the set in descending order,
+    /// starting at the given key, or at the next smaller key if it is absent.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..5 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = set.iter_from_key_rev(&20).collect();
+    /// assert_eq!(keys, [&20, &10, &0]);
+    /// ```
+    pub fn iter_from_key_rev<Q>(&self, key: &Q) -> Rev<IndexTreeSetRange<'_, K>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        IndexTreeSetRange {
+            range: IndexTreeRange::new(
+                &self.map.root,
+                self.map.size,
+                0,
+                self.map.upper_bound_index(key),
+            ),
+        }
+        .rev()
+    }
+}
+
+impl<K> IndexTreeSet<K> {
+    /// Constructs an iterator over the keys of the set starting at the given index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..5 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = set.iter_from_index(3).collect();
+    /// assert_eq!(keys, [&30, &40]);
+    /// ```
+    pub fn iter_from_index(&self, index: usize) -> IndexTreeSetRange<'_, K> {
+        IndexTreeSetRange {
+            range: self.map.iter_from_index(index),
+        }
+    }
+
+    /// Constructs an iterator over the keys of the set in descending order, starting at the given index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..5 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let keys: Vec<_> = set.iter_from_index_rev(1).collect();
+    /// assert_eq!(keys, [&10, &0]);
+    /// ```
+    pub fn iter_from_index_rev(&self, index: usize) -> Rev<IndexTreeSetRange<'_, K>> {
+        let end = index.saturating_add(1).min(self.len());
+        IndexTreeSetRange {
+            range: IndexTreeRange::new(&self.map.root, self.map.size, 0, end),
+        }
+        .rev()
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Removes an item from the map from its corresponding key, returning the key-value pair that was previously in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(1);
+    /// assert_eq!(tree.remove(&1), Some(1));
+    /// assert_eq!(tree.remove(&2), None);
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(key).map(|(k, _)| k)
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Removes an item from the map from its corresponding index, returning the key-value pair that was previously in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(1);
+    /// assert_eq!(tree.remove_from_index(0), Some(1));
+    /// assert_eq!(tree.remove_from_index(1), None);
+    /// ```
+    pub fn remove_from_index(&mut self, index: usize) -> Option<K> {
+        self.map.remove_from_index(index).map(|(k, _)| k)
+    }
+}
+
+impl<K> IndexTreeSet<K> {
+    /// Removes and returns the first key in the set.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(1);
+    /// tree.insert(2);
+    /// assert_eq!(tree.pop_first(), Some(1));
+    /// assert_eq!(tree.pop_first(), Some(2));
+    /// assert_eq!(tree.pop_first(), None);
+    /// ```
+    pub fn pop_first(&mut self) -> Option<K> {
+        self.map.pop_first().map(|(k, _)| k)
+    }
+
+    /// Removes and returns the last key in the set.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::new();
+    /// tree.insert(1);
+    /// tree.insert(2);
+    /// assert_eq!(tree.pop_last(), Some(2));
+    /// assert_eq!(tree.pop_last(), Some(1));
+    /// assert_eq!(tree.pop_last(), None);
+    /// ```
+    pub fn pop_last(&mut self) -> Option<K> {
+        self.map.pop_last().map(|(k, _)| k)
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Splits the map into two at the given key. Returns everything after the given key, including the key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut a = IndexTreeSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(13);
+    /// a.insert(17);
+    /// a.insert(41);
+    ///
+    /// let b = a.split_off(&13);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    pub fn split_off<Q>(&mut self, key: &Q) -> IndexTreeSet<K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        IndexTreeSet {
+            map: self.map.split_off(key),
+        }
+    }
+}
+
+impl<K> IndexTreeSet<K> {
+    /// Splits the map into two at the given index. Returns everything after the given key, including the key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut a = IndexTreeSet::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(3);
+    /// a.insert(17);
+    /// a.insert(41);
+    ///
+    /// let b = a.split_off_from_index(2);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    pub fn split_off_from_index(&mut self, index: usize) -> IndexTreeSet<K> {
+        IndexTreeSet {
+            map: self.map.split_off_from_index(index),
+        }
+    }
+
+    /// Removes the keys at the given range of indexes.
+    ///
+    /// Whole subtrees inside the range are detached at once, so removing `k` keys takes `O(log n + k)` time.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::from_sorted_iter(0..10);
+    /// tree.remove_range(2..8);
+    /// assert!(tree.iter().copied().eq([0, 1, 8, 9]));
+    /// ```
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.map.remove_range(range)
+    }
+
+    /// Removes the keys at the given range of indexes and returns them as an iterator in ascending order.
+    ///
+    /// The keys are detached from the set before the iterator is returned, so they are removed even if
+    /// the iterator is dropped without being consumed.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::from_sorted_iter(0..10);
+    /// let drained: Vec<_> = tree.drain_range(..3).collect();
+    /// assert_eq!(drained, vec![0, 1, 2]);
+    /// assert_eq!(tree.len(), 7);
+    /// ```
+    pub fn drain_range<R: RangeBounds<usize>>(&mut self, range: R) -> IndexTreeSetIntoIterator<K> {
+        let (start, end) = index_range_from_bounds(&range, self.map.size);
+        IndexTreeSet {
+            map: self.map.take_range(start, end),
+        }
+        .into_iter()
+    }
+}
+
+impl<K: Ord> IndexTreeSet<K> {
+    /// Removes the keys that fall in the given range.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::from_sorted_iter(0..10);
+    /// tree.remove_key_range(3..=6);
+    /// assert!(tree.iter().copied().eq([0, 1, 2, 7, 8, 9]));
+    /// ```
+    pub fn remove_key_range<Q, R>(&mut self, range: R)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.map.remove_key_range(range)
+    }
+
+    /// Removes the keys that fall in the given range and returns them as an iterator in ascending order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut tree = IndexTreeSet::from_sorted_iter(0..10);
+    /// let drained: Vec<_> = tree.drain_key_range(7..).collect();
+    /// assert_eq!(drained, vec![7, 8, 9]);
+    /// assert_eq!(tree.len(), 7);
+    /// ```
+    pub fn drain_key_range<Q, R>(&mut self, range: R) -> IndexTreeSetIntoIterator<K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.map.index_range_from_key_range(&range);
+        IndexTreeSet {
+            map: self.map.take_range(start, end),
+        }
+        .into_iter()
+    }
+}
+
+/// The 'Map' IndexTree data structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexTreeMap<K, V> {
+    pub root: Box<Node<K, V>>,
+    pub size: usize,
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Makes a new, empty IndexTreeMap.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// ### Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    ///
+    /// map.insert(1, "a".to_string());
+    ///
+    /// ```    
+    pub fn new() -> IndexTreeMap<K, V> {
+        IndexTreeMap::default()
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Builds a map in linear time from key-value pairs whose keys are already in strictly ascending order.
+    ///
+    /// The keys are not checked: if they are out of order or repeated, the map is still safe to use,
+    /// but lookups by key may return wrong results. Use [`IndexTreeMap::try_from_sorted_iter`] to check them.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from_sorted_iter((0..100).map(|i| (i, i * 2)));
+    /// assert_eq!(map.len(), 100);
+    /// assert_eq!(map.get(&42), Some(&84));
+    /// ```
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> IndexTreeMap<K, V> {
+        let items: Vec<_> = iter
+            .into_iter()
+            .map(|(key, value)| Box::new(Item::new(key, value)))
+            .collect();
+        IndexTreeMap::from_sorted_items(items.into_iter())
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Builds a map in linear time from key-value pairs whose keys are in strictly ascending order,
+    /// or returns an error naming the first key that is out of order or repeated.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::try_from_sorted_iter([(1, "a"), (2, "b")]).unwrap();
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// let error = IndexTreeMap::try_from_sorted_iter([(2, "b"), (1, "a")]).unwrap_err();
+    /// assert_eq!(error.index, 1);
+    /// ```
+    pub fn try_from_sorted_iter<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<IndexTreeMap<K, V>, UnsortedKeyError> {
+        IndexTreeMap::try_from_sorted_pairs(iter.into_iter())
+    }
+}
+
+impl<K, V> Default for IndexTreeMap<K, V> {
+    fn default() -> Self {
+        IndexTreeMap {
+            root: Node::new(),
+            size: 0,
+        }
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Clears the map, removing all elements.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    ///
+    /// map.insert(1, "a".to_string());
+    /// map.clear();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.root = Node::new();
+        self.size = 0
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Retains only the elements specified by the predicate, visiting them in ascending key order.
+    ///
+    /// Each rejected element is removed in place, so only the nodes that lose elements are rebalanced.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree: IndexTreeMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// tree.retain(|&k, _| k % 2 == 0);
+    /// assert!(tree.keys().eq([0, 2, 4, 6].iter()));
+    /// assert_eq!(tree.get_index_from_key(&4), Some(2));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.extract_if(|key, value| !f(key, value)).for_each(drop);
+    }
+
+    /// Creates an iterator that visits all elements in ascending key order,
+    /// and removes and yields those for which the predicate returns `true`.
+    ///
+    /// Elements that are not visited because the iterator is dropped early are kept.
+    /// Each element stays in the map until the predicate has returned for it,
+    /// so a panicking predicate leaves the map whole.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree: IndexTreeMap<i32, i32> = (0..8).map(|x| (x, x)).collect();
+    /// let evens: Vec<(i32, i32)> = tree.extract_if(|k, _| k % 2 == 0).collect();
+    /// assert_eq!(evens, [(0, 0), (2, 2), (4, 4), (6, 6)]);
+    /// assert!(tree.keys().eq([1, 3, 5, 7].iter()));
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Gets the number of items in the map
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    ///
+    /// map.insert(1, "a".to_string());
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if the map contains no elements.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// assert!(map.is_empty());
+    /// map.insert(1, "a");
+    /// assert!(!map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Returns true if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.contains_key(&1), true);
+    /// assert_eq!(tree.contains_key(&2), false);
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.get(key).is_some()
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Returns true if the map contains an item in the index position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.contains_index(0), true);
     /// assert_eq!(tree.contains_index(1), false);
     /// ```
-    pub fn contains_index(&self, index: usize) -> bool {
-        index < self.size
+    pub fn contains_index(&self, index: usize) -> bool {
+        index < self.size
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get(&1), Some(&"a".to_string()));
+    /// assert_eq!(tree.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.get(key).map(|item| item.1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_mut(&1), Some(&mut "a".to_string()));
+    /// assert_eq!(tree.get_mut(&2), None);
+    /// ```
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.get_mut(key).map(|item| item.1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The supplied key may be any borrowed form of the map’s key type, but
+    /// the ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_key_value(&1), Some((&1, &"a".to_string())));
+    /// assert_eq!(tree.get_key_value(&2), None);
+    /// ```
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.get(key)
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Returns a reference to the value corresponding to the index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_from_index(0), Some(&"a".to_string()));
+    /// assert_eq!(tree.get_from_index(1), None);
+    /// ```
+    pub fn get_from_index(&self, index: usize) -> Option<&V> {
+        if index < self.size {
+            match self.root.get_from_index(index) {
+                None => None,
+                Some(item) => Some(item.1),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value corresponding to the index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_mut_from_index(0), Some(&mut "a".to_string()));
+    /// assert_eq!(tree.get_mut_from_index(1), None);
+    /// ```
+    pub fn get_mut_from_index(&mut self, id: usize) -> Option<&mut V> {
+        self.root.get_mut_from_index(id).map(|item| item.1)
+    }
+
+    /// Returns the index of the corresponding key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_index_from_key(&1), Some(0));
+    /// assert_eq!(tree.get_index_from_key(&2), None);
+    /// ```
+    pub fn get_index_from_key<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let usize = 0;
+        self.root.get_index_from_key(key, usize)
+    }
+
+    /// Returns the index of the key if it is in the map, otherwise the index where it would be inserted.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.rank(&20), Ok(1));
+    /// assert_eq!(tree.rank(&15), Err(1));
+    /// assert_eq!(tree.rank(&25), Err(2));
+    /// ```
+    pub fn rank<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.root.rank(key)
+    }
+
+    /// Returns the index of the first key in the map that is not less than the given key,
+    /// i.e. the number of keys that are less than the given key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.lower_bound_index(&10), 0);
+    /// assert_eq!(tree.lower_bound_index(&15), 1);
+    /// assert_eq!(tree.lower_bound_index(&25), 2);
+    /// ```
+    pub fn lower_bound_index<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.root.rank(key) {
+            Ok(index) | Err(index) => index,
+        }
+    }
+
+    /// Returns the index of the first key in the map that is greater than the given key,
+    /// i.e. the number of keys that are less than or equal to the given key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.upper_bound_index(&5), 0);
+    /// assert_eq!(tree.upper_bound_index(&10), 1);
+    /// assert_eq!(tree.upper_bound_index(&20), 2);
+    /// ```
+    pub fn upper_bound_index<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.root.rank(key) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        }
+    }
+
+    /// Returns a reference to the key corresponding to the index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_key_from_index(0), Some(&1));
+    /// assert_eq!(tree.get_key_from_index(1), None);
+    /// ```
+    pub fn get_key_from_index(&self, id: usize) -> Option<&K> {
+        if self.contains_index(id) {
+            self.root.get_from_index(id).map(|item| item.0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the key-value pair corresponding to the index.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_key_value_from_index(0), Some((&1, &"a".to_string())));
+    /// assert_eq!(tree.get_key_value_from_index(1), None);
+    /// ```
+    pub fn get_key_value_from_index(&self, id: usize) -> Option<(&K, &V)> {
+        if self.contains_index(id) {
+            self.root.get_from_index(id)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the first key in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_first_key(), Some(&1));
+    /// ```
+    pub fn get_first_key(&self) -> Option<&K> {
+        self.root.get_from_index(0).map(|item| item.0)
+    }
+
+    /// Returns a reference to the first value in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_first_value(), Some(&"a".to_string()));
+    /// ```
+    pub fn get_first_value(&self) -> Option<&V> {
+        self.root.get_from_index(0).map(|item| item.1)
+    }
+
+    /// Returns a reference to the first key-value pair in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// assert_eq!(tree.get_first_key_value(), Some((&1, &"a".to_string())));
+    /// ```
+    pub fn get_first_key_value(&self) -> Option<(&K, &V)> {
+        self.root.get_from_index(0)
+    }
+
+    /// Returns a reference to the last key in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// assert_eq!(tree.get_last_key(), Some(&2));
+    /// ```
+    pub fn get_last_key(&self) -> Option<&K> {
+        self.size
+            .checked_sub(1)
+            .and_then(|index| self.root.get_from_index(index))
+            .map(|item| item.0)
+    }
+
+    /// Returns a reference to the first value in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// assert_eq!(tree.get_last_value(), Some(&"b".to_string()));
+    /// ```
+    pub fn get_last_value(&self) -> Option<&V> {
+        self.size
+            .checked_sub(1)
+            .and_then(|index| self.root.get_from_index(index))
+            .map(|item| item.1)
+    }
+
+    /// Returns a reference to the last key-value pair in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// assert_eq!(tree.get_last_key_value(), Some((&2, &"b".to_string())));
+    /// ```
+    pub fn get_last_key_value(&self) -> Option<(&K, &V)> {
+        self.size
+            .checked_sub(1)
+            .and_then(|index| self.root.get_from_index(index))
     }
 }
 
 impl<K: Ord, V> IndexTreeMap<K, V> {
-    /// Returns a reference to the value corresponding to the key.
+    /// Returns the index, key and value of the greatest key in the map that is less than or equal to the given key.
     ///
-    /// The key may be any borrowed form of the map’s key type, but the
-    /// ordering on the borrowed form must match the ordering on the key type.
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.floor(&15), Some((0, &10, &"a")));
+    /// assert_eq!(tree.floor(&20), Some((1, &20, &"b")));
+    /// assert_eq!(tree.floor(&5), None);
+    /// ```
+    pub fn floor<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.upper_bound_index(key).checked_sub(1)?;
+        self.get_key_value_from_index(index)
+            .map(|(key, value)| (index, key, value))
+    }
+
+    /// Returns the index, key and value of the least key in the map that is greater than or equal to the given key.
     ///
     /// # Example
     ///
@@ -539,18 +1719,47 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// use indextreemap::IndexTreeMap;
     ///
     /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get(&1), Some(&"a".to_string()));
-    /// assert_eq!(tree.get(&2), None);
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.ceiling(&15), Some((1, &20, &"b")));
+    /// assert_eq!(tree.ceiling(&10), Some((0, &10, &"a")));
+    /// assert_eq!(tree.ceiling(&25), None);
     /// ```
-    pub fn get(&self, key: &K) -> Option<&V> {
-        self.root.get(key).map(|item| item.1)
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.lower_bound_index(key);
+        self.get_key_value_from_index(index)
+            .map(|(key, value)| (index, key, value))
     }
 
-    /// Returns a mutable reference to the value corresponding to the key.
+    /// Returns the index, key and value of the greatest key in the map that is strictly less than the given key.
     ///
-    /// The key may be any borrowed form of the map’s key type, but the
-    /// ordering on the borrowed form must match the ordering on the key type.
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.predecessor(&20), Some((0, &10, &"a")));
+    /// assert_eq!(tree.predecessor(&10), None);
+    /// ```
+    pub fn predecessor<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.lower_bound_index(key).checked_sub(1)?;
+        self.get_key_value_from_index(index)
+            .map(|(key, value)| (index, key, value))
+    }
+
+    /// Returns the index, key and value of the least key in the map that is strictly greater than the given key.
     ///
     /// # Example
     ///
@@ -559,18 +1768,25 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// use indextreemap::IndexTreeMap;
     ///
     /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_mut(&1), Some(&mut "a".to_string()));
-    /// assert_eq!(tree.get_mut(&2), None);
+    /// tree.insert(10, "a");
+    /// tree.insert(20, "b");
+    /// assert_eq!(tree.successor(&10), Some((1, &20, &"b")));
+    /// assert_eq!(tree.successor(&20), None);
     /// ```
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
-        self.root.get_mut(key).map(|item| item.1)
+    pub fn successor<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.upper_bound_index(key);
+        self.get_key_value_from_index(index)
+            .map(|(key, value)| (index, key, value))
     }
 
-    /// Returns the key-value pair corresponding to the supplied key.
+    /// Returns the index, key and value `offset` positions after the given key, or before it if `offset` is negative.
     ///
-    /// The supplied key may be any borrowed form of the map’s key type, but
-    /// the ordering on the borrowed form must match the ordering on the key type.
+    /// The key does not need to be in the map: an offset of `1` gives the least key greater than it,
+    /// and an offset of `-1` gives the greatest key less than it. An offset of `0` gives the key itself, if present.
     ///
     /// # Example
     ///
@@ -578,18 +1794,35 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_key_value(&1), Some((&1, &"a".to_string())));
-    /// assert_eq!(tree.get_key_value(&2), None);
+    /// let tree = IndexTreeMap::from([(10, "a"), (20, "b"), (30, "c"), (40, "d")]);
+    /// assert_eq!(tree.nth_from_key(&20, 2), Some((3, &40, &"d")));
+    /// assert_eq!(tree.nth_from_key(&20, -1), Some((0, &10, &"a")));
+    /// assert_eq!(tree.nth_from_key(&25, 1), Some((2, &30, &"c")));
+    /// assert_eq!(tree.nth_from_key(&25, 0), None);
+    /// assert_eq!(tree.nth_from_key(&40, 1), None);
     /// ```
-    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
-        self.root.get(key)
+    pub fn nth_from_key<Q>(&self, key: &Q, offset: isize) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // the indexes of the first key not less than and the first key greater than the given key
+        let (lower, upper) = match self.rank(key) {
+            Ok(index) => (index, index + 1),
+            Err(index) => (index, index),
+        };
+        let index = match offset.cmp(&0) {
+            Greater => upper.checked_add(offset.unsigned_abs() - 1)?,
+            Less => lower.checked_sub(offset.unsigned_abs())?,
+            Equal => (lower < upper).then_some(lower)?,
+        };
+        self.get_key_value_from_index(index)
+            .map(|(key, value)| (index, key, value))
     }
 }
 
 impl<K: Ord, V> IndexTreeMap<K, V> {
-    /// Returns a reference to the value corresponding to the index.
+    /// Inserts a key-value pair into the map.  
     ///
     /// # Example
     ///
@@ -599,38 +1832,306 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     ///
     /// let mut tree = IndexTreeMap::new();
     /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_from_index(0), Some(&"a".to_string()));
-    /// assert_eq!(tree.get_from_index(1), None);
+    /// assert!(!tree.is_empty());
     /// ```
-    pub fn get_from_index(&self, index: usize) -> Option<&V> {
-        if index < self.size {
-            match self.root.get_from_index(index) {
-                None => None,
-                Some(item) => Some(item.1),
+    pub fn insert(&mut self, key: K, value: V) {
+        self.insert_full(key, value);
+    }
+
+    /// Inserts a key-value pair into the map, returning the index of the key
+    /// and the value it replaced, if the key was already present.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// assert_eq!(tree.insert_full(3, "a"), (0, None));
+    /// assert_eq!(tree.insert_full(1, "b"), (0, None));
+    /// assert_eq!(tree.insert_full(3, "c"), (1, Some("a")));
+    /// ```
+    pub fn insert_full(&mut self, key: K, value: V) -> (usize, Option<V>) {
+        let mut rank = 0;
+        match self.root.insert_full(key, value, &mut rank) {
+            KeyExists(value) => return (rank, Some(value)),
+            NewKeyPointer(new_key, new_pointer) => {
+                self.root.update_root(new_key, new_pointer);
+                self.size += 1
             }
-        } else {
-            None
+            _ => self.size += 1,
         }
+        (rank, None)
     }
 
-    /// Returns a mutable reference to the value corresponding to the index.
+    /// Tries to insert a key-value pair into the map, returning the index of the key and a mutable reference to the value.
+    ///
+    /// If the key is already present, the map is left unchanged and an error containing the occupied entry
+    /// and the rejected value is returned.
     ///
     /// # Example
     ///
     /// Basic usage:
     /// ```rust
+    /// use indextreemap::methods::entry::OccupiedError;
     /// use indextreemap::IndexTreeMap;
     ///
     /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_mut_from_index(0), Some(&mut "a".to_string()));
-    /// assert_eq!(tree.get_mut_from_index(1), None);
+    /// assert_eq!(tree.try_insert(37, "a").unwrap(), (0, &mut "a"));
+    /// assert_eq!(tree.try_insert(12, "c").unwrap(), (0, &mut "c"));
+    ///
+    /// match tree.try_insert(37, "b") {
+    ///     Err(OccupiedError { entry, value }) => {
+    ///         assert_eq!(entry.index(), 1);
+    ///         assert_eq!(entry.get(), &"a");
+    ///         assert_eq!(value, "b");
+    ///     }
+    ///     Ok(_) => unreachable!(),
+    /// }
+    /// assert_eq!(tree.get(&37), Some(&"a"));
     /// ```
-    pub fn get_mut_from_index(&mut self, id: usize) -> Option<&mut V> {
-        self.root.get_mut_from_index(id).map(|item| item.1)
+    pub fn try_insert(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(usize, &mut V), OccupiedError<'_, K, V>> {
+        match Handle::new(self, |node| node.search_keys(&key)) {
+            (handle, true) => Err(OccupiedError {
+                entry: OccupiedEntry { handle },
+                value,
+            }),
+            (handle, false) => {
+                let index = handle.index();
+                Ok((index, handle.insert(key, value)))
+            }
+        }
     }
+}
 
-    /// Returns the index of the corresponding key.
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// Both variants of the entry report the index of the key, or the index the key will have once inserted.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut count = IndexTreeMap::new();
+    ///
+    /// for word in ["b", "a", "c", "a"] {
+    ///     count.entry(word).and_modify(|n| *n += 1).or_insert(1);
+    /// }
+    /// assert_eq!(count.get(&"a"), Some(&2));
+    ///
+    /// assert_eq!(count.entry("b").index(), 1);
+    /// assert_eq!(count.entry("bb").index(), 2);
+    /// assert_eq!(*count.entry("d").or_default(), 0);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match Handle::new(self, |node| node.search_keys(&key)) {
+            (handle, true) => Entry::Occupied(OccupiedEntry { handle }),
+            (handle, false) => Entry::Vacant(VacantEntry { handle, key }),
+        }
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(10, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let (first_key, first_value) = map.iter().next().unwrap();
+    /// assert_eq!((first_key, first_value), (&1, &"a"));
+    /// ```
+    pub fn iter(&self) -> IndexTreeIterator<'_, K, V> {
+        IndexTreeIterator::new(&self.root, self.size, 0, self.size)
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Gets an iterator over the keys of the map, in sorted order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(1, "a");
+    ///
+    /// let first_key = map.keys().next().unwrap();
+    /// assert_eq!(first_key, &1);
+    /// ```
+    pub fn keys(&self) -> IndexTreeKeys<'_, K, V> {
+        IndexTreeKeys { iter: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map, in sorted order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(1, "a");
+    ///
+    /// let first_value = map.values().next().unwrap();
+    /// assert_eq!(first_value, &"a");
+    /// ```
+    pub fn values(&self) -> IndexTreeValues<'_, K, V> {
+        IndexTreeValues { iter: self.iter() }
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    ///
+    /// for (_, value) in map.iter_mut() {
+    ///     *value += 10;
+    /// }
+    /// assert_eq!(map.get(&"a"), Some(&11));
+    /// ```
+    pub fn iter_mut(&mut self) -> IndexTreeRangeMut<'_, K, V> {
+        IndexTreeRangeMut::new(&mut self.root, self.size, 0, self.size)
+    }
+
+    /// Gets a mutable iterator over the values of the map, in order by key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(1, "a".to_string());
+    /// map.insert(2, "b".to_string());
+    ///
+    /// for value in map.values_mut() {
+    ///     value.push('!');
+    /// }
+    /// assert_eq!(map.get(&2), Some(&"b!".to_string()));
+    /// ```
+    pub fn values_mut(&mut self) -> IndexTreeValuesMut<'_, K, V> {
+        IndexTreeValuesMut {
+            iter: self.iter_mut(),
+        }
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    /// Creates a consuming iterator visiting all the keys, in sorted order. The map cannot be used after calling this.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let keys: Vec<i32> = map.into_keys().collect();
+    /// assert_eq!(keys, [1, 2]);
+    /// ```
+    pub fn into_keys(self) -> IndexTreeIntoKeys<K, V> {
+        IndexTreeIntoKeys {
+            iter: self.into_iter(),
+        }
+    }
+
+    /// Creates a consuming iterator visiting all the values, in order by key. The map cannot be used after calling this.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let values: Vec<&str> = map.into_values().collect();
+    /// assert_eq!(values, ["a", "b"]);
+    /// ```
+    pub fn into_values(self) -> IndexTreeIntoValues<K, V> {
+        IndexTreeIntoValues {
+            iter: self.into_iter(),
+        }
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Constructs an iterator over a sub-range of entries in the map, sorted by key.
+    ///
+    /// The index of the first entry in the range is held in the `start` field of the iterator,
+    /// and the index of the next entry to be returned in the `index` field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(3, "a");
+    /// map.insert(5, "b");
+    /// map.insert(8, "c");
+    ///
+    /// let mut range = map.range(4..=8);
+    /// assert_eq!(range.start, 1);
+    /// assert_eq!(range.next(), Some((&5, &"b")));
+    /// assert_eq!(range.next(), Some((&8, &"c")));
+    /// assert_eq!(range.next(), None);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> IndexTreeRange<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.index_range_from_key_range(&range);
+        IndexTreeRange::new(&self.root, self.size, start, end)
+    }
+
+    /// Returns the number of entries in the map whose keys fall in the given range, without iterating over them.
+    ///
+    /// The count is the difference of the indexes of the two bounds, which are found from the subtree sizes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Example
     ///
@@ -638,17 +2139,26 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_index_from_key(&1), Some(0));
-    /// assert_eq!(tree.get_index_from_key(&2), None);
+    /// let map = IndexTreeMap::from([(3, "a"), (5, "b"), (8, "c")]);
+    /// assert_eq!(map.count_range(4..=8), 2);
+    /// assert_eq!(map.count_range(..), 3);
+    /// assert_eq!(map.count_range(6..8), 0);
     /// ```
-    pub fn get_index_from_key(&self, key: &K) -> Option<usize> {
-        let usize = 0;
-        self.root.get_index_from_key(key, usize)
+    pub fn count_range<Q, R>(&self, range: R) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.index_range_from_key_range(&range);
+        end - start
     }
 
-    /// Returns a reference to the key corresponding to the index.
+    /// Constructs a mutable iterator over a sub-range of entries in the map, sorted by key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Example
     ///
@@ -656,20 +2166,37 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_key_from_index(0), Some(&1));
-    /// assert_eq!(tree.get_key_from_index(1), None);
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(3, 30);
+    /// map.insert(5, 50);
+    /// map.insert(8, 80);
+    ///
+    /// for (_, value) in map.range_mut(4..) {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(map.get(&3), Some(&30));
+    /// assert_eq!(map.get(&5), Some(&51));
+    /// assert_eq!(map.get(&8), Some(&81));
     /// ```
-    pub fn get_key_from_index(&self, id: usize) -> Option<&K> {
-        if self.contains_index(id) {
-            self.root.get_from_index(id).map(|item| item.0)
-        } else {
-            None
-        }
+    pub fn range_mut<Q, R>(&mut self, range: R) -> IndexTreeRangeMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.index_range_from_key_range(&range);
+        IndexTreeRangeMut::new(&mut self.root, self.size, start, end)
     }
+}
 
-    /// Returns a reference to the key-value pair corresponding to the index.
+impl<K, V> IndexTreeMap<K, V> {
+    /// Constructs an iterator over the entries between two index positions, sorted by key.
+    ///
+    /// The end of the range is clamped to the length of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
     ///
     /// # Example
     ///
@@ -677,20 +2204,28 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_key_value_from_index(0), Some((&1, &"a".to_string())));
-    /// assert_eq!(tree.get_key_value_from_index(1), None);
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let mut range = map.range_by_index(3..=4);
+    /// assert_eq!(range.next(), Some((&30, &3)));
+    /// assert_eq!(range.next(), Some((&40, &4)));
+    /// assert_eq!(range.next(), None);
     /// ```
-    pub fn get_key_value_from_index(&self, id: usize) -> Option<(&K, &V)> {
-        if self.contains_index(id) {
-            self.root.get_from_index(id)
-        } else {
-            None
-        }
+    pub fn range_by_index<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeRange<'_, K, V> {
+        let (start, end) = index_range_from_bounds(&range, self.size);
+        IndexTreeRange::new(&self.root, self.size, start, end)
     }
 
-    /// Returns a reference to the first key in the map.
+    /// Constructs a mutable iterator over the entries between two index positions, sorted by key.
+    ///
+    /// The end of the range is clamped to the length of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
     ///
     /// # Example
     ///
@@ -698,15 +2233,33 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_first_key(), Some(&1));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// for (_, value) in map.range_mut_by_index(3..=4) {
+    ///     *value = 0;
+    /// }
+    /// assert_eq!(map.get(&30), Some(&0));
+    /// assert_eq!(map.get(&50), Some(&5));
     /// ```
-    pub fn get_first_key(&self) -> Option<&K> {
-        self.root.get_from_index(0).map(|item| item.0)
+    pub fn range_mut_by_index<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> IndexTreeRangeMut<'_, K, V> {
+        let (start, end) = index_range_from_bounds(&range, self.size);
+        IndexTreeRangeMut::new(&mut self.root, self.size, start, end)
     }
 
-    /// Returns a reference to the first value in the map.
+    /// Returns a borrowed view of the entries between two index positions.
+    ///
+    /// Indexes into the view are relative to the start of the view, and the view can be sliced further.
+    /// The end of the range is clamped to the length of the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
     ///
     /// # Example
     ///
@@ -714,15 +2267,32 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_first_value(), Some(&"a".to_string()));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let page = map.slice(2..8);
+    /// assert_eq!(page.len(), 6);
+    /// assert_eq!(page.get(0), Some((&20, &2)));
+    ///
+    /// let sub_page = page.slice(4..);
+    /// assert_eq!(sub_page.len(), 2);
+    /// assert_eq!(sub_page.get(1), Some((&70, &7)));
+    /// assert_eq!(sub_page.get(2), None);
     /// ```
-    pub fn get_first_value(&self) -> Option<&V> {
-        self.root.get_from_index(0).map(|item| item.1)
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeSlice<'_, K, V> {
+        let (start, end) = index_range_from_bounds(&range, self.size);
+        IndexTreeSlice {
+            tree: self,
+            start,
+            end,
+        }
     }
+}
 
-    /// Returns a reference to the first key-value pair in the map.
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Constructs an iterator over the entries of the map starting at the given key, or at the next greater key if it is absent.
     ///
     /// # Example
     ///
@@ -730,15 +2300,31 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert_eq!(tree.get_first_key_value(), Some((&1, &"a".to_string())));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..5 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let mut iter = map.iter_from_key(&15);
+    /// assert_eq!(iter.index, 2);
+    /// assert_eq!(iter.next(), Some((&20, &2)));
+    /// assert_eq!(iter.next(), Some((&30, &3)));
     /// ```
-    pub fn get_first_key_value(&self) -> Option<(&K, &V)> {
-        self.root.get_from_index(0)
+    pub fn iter_from_key<Q>(&self, key: &Q) -> IndexTreeRange<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        IndexTreeRange::new(
+            &self.root,
+            self.size,
+            self.lower_bound_index(key),
+            self.size,
+        )
     }
 
-    /// Returns a reference to the last key in the map.
+    /// Constructs an iterator over the entries of the map in descending order,
+    /// starting at the given key, or at the next smaller key if it is absent.
     ///
     /// # Example
     ///
@@ -746,16 +2332,27 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// tree.insert(2, "b".to_string());
-    /// assert_eq!(tree.get_last_key(), Some(&2));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..5 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let mut iter = map.iter_from_key_rev(&15);
+    /// assert_eq!(iter.next(), Some((&10, &1)));
+    /// assert_eq!(iter.next(), Some((&0, &0)));
+    /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn get_last_key(&self) -> Option<&K> {
-        self.root.get_from_index(self.size - 1).map(|item| item.0)
+    pub fn iter_from_key_rev<Q>(&self, key: &Q) -> Rev<IndexTreeRange<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        IndexTreeRange::new(&self.root, self.size, 0, self.upper_bound_index(key)).rev()
     }
+}
 
-    /// Returns a reference to the first value in the map.
+impl<K, V> IndexTreeMap<K, V> {
+    /// Constructs an iterator over the entries of the map starting at the given index.
     ///
     /// # Example
     ///
@@ -763,16 +2360,21 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// tree.insert(2, "b".to_string());
-    /// assert_eq!(tree.get_last_value(), Some(&"b".to_string()));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..5 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let mut iter = map.iter_from_index(3);
+    /// assert_eq!(iter.next(), Some((&30, &3)));
+    /// assert_eq!(iter.next(), Some((&40, &4)));
+    /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn get_last_value(&self) -> Option<&V> {
-        self.root.get_from_index(self.size - 1).map(|item| item.1)
+    pub fn iter_from_index(&self, index: usize) -> IndexTreeRange<'_, K, V> {
+        IndexTreeRange::new(&self.root, self.size, index.min(self.size), self.size)
     }
 
-    /// Returns a reference to the last key-value pair in the map.
+    /// Constructs an iterator over the entries of the map in descending order, starting at the given index.
     ///
     /// # Example
     ///
@@ -780,18 +2382,24 @@ impl<K: Ord, V> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// tree.insert(2, "b".to_string());
-    /// assert_eq!(tree.get_last_key_value(), Some((&2, &"b".to_string())));
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..5 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let mut iter = map.iter_from_index_rev(1);
+    /// assert_eq!(iter.next(), Some((&10, &1)));
+    /// assert_eq!(iter.next(), Some((&0, &0)));
+    /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn get_last_key_value(&self) -> Option<(&K, &V)> {
-        self.root.get_from_index(self.size - 1)
+    pub fn iter_from_index_rev(&self, index: usize) -> Rev<IndexTreeRange<'_, K, V>> {
+        let end = index.saturating_add(1).min(self.size);
+        IndexTreeRange::new(&self.root, self.size, 0, end).rev()
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
-    /// Inserts a key-value pair into the map.  
+impl<K, V> IndexTreeMap<K, V> {
+    /// Returns a cursor pointing at the first entry of the map, or at the ghost position if the map is empty.
     ///
     /// # Example
     ///
@@ -799,24 +2407,23 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     /// ```rust
     /// use indextreemap::IndexTreeMap;
     ///
-    /// let mut tree = IndexTreeMap::new();
-    /// tree.insert(1, "a".to_string());
-    /// assert!(!tree.is_empty());
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// assert_eq!(cursor.current(), Some((&1, &"a")));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.index(), Some(1));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), None);
     /// ```
-    pub fn insert(&mut self, key: K, value: V) {
-        match self.root.insert(key, value) {
-            KeyExists => {}
-            NewKeyPointer(new_key, new_pointer) => {
-                self.root.update_root(new_key, new_pointer);
-                self.size += 1
-            }
-            _ => self.size += 1,
-        }
+    pub fn cursor_front(&self) -> Cursor<'_, K, V> {
+        Cursor::new(self, 0)
     }
-}
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Gets an iterator over the entries of the map, sorted by key.
+    /// Returns a cursor pointing at the last entry of the map, or at the ghost position if the map is empty.
     ///
     /// # Example
     ///
@@ -825,22 +2432,20 @@ impl<K, V> IndexTreeMap<K, V> {
     /// use indextreemap::IndexTreeMap;
     ///
     /// let mut map = IndexTreeMap::new();
-    /// map.insert(10, "b");
     /// map.insert(1, "a");
+    /// map.insert(2, "b");
     ///
-    /// let (first_key, first_value) = map.iter().next().unwrap();
-    /// assert_eq!((first_key, first_value), (&1, &"a"));
+    /// let mut cursor = map.cursor_back();
+    /// assert_eq!(cursor.current(), Some((&2, &"b")));
+    /// assert!(cursor.seek_key(&1));
+    /// assert_eq!(cursor.index(), Some(0));
     /// ```
-    pub fn iter(&self) -> IndexTreeIterator<K, V> {
-        IndexTreeIterator {
-            tree: self,
-            index: 0,
-        }
+    pub fn cursor_back(&self) -> Cursor<'_, K, V> {
+        Cursor::new(self, self.size.saturating_sub(1))
     }
-}
 
-impl<K, V> IndexTreeMap<K, V> {
-    /// Gets an iterator over the keys of the map, in sorted order.
+    /// Returns a mutable cursor pointing at the first entry of the map, or at the ghost position if the map is empty.
+    /// The cursor holds the nodes on its way to the current entry, so the map can be used again once it is dropped.
     ///
     /// # Example
     ///
@@ -850,18 +2455,23 @@ impl<K, V> IndexTreeMap<K, V> {
     ///
     /// let mut map = IndexTreeMap::new();
     /// map.insert(1, "a");
+    /// map.insert(3, "c");
     ///
-    /// let first_key = map.keys().next().unwrap();
-    /// assert_eq!(first_key, &1);
+    /// let mut cursor = map.cursor_front_mut();
+    /// assert!(cursor.insert_after(2, "b").is_ok());
+    /// assert!(cursor.insert_after(4, "d").is_err());
+    /// assert_eq!(cursor.remove_current(), Some((1, "a")));
+    /// assert_eq!(cursor.current(), Some((&2, &"b")));
+    ///
+    /// // the map is whole again once the cursor is dropped
+    /// drop(cursor);
+    /// assert!(map.keys().eq([2, 3].iter()));
     /// ```
-    pub fn keys(&self) -> IndexTreeKeys<K, V> {
-        IndexTreeKeys {
-            tree: self,
-            index: 0,
-        }
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut::new(self, 0)
     }
 
-    /// Gets an iterator over the values of the map, in sorted order.
+    /// Returns a mutable cursor pointing at the last entry of the map, or at the ghost position if the map is empty.
     ///
     /// # Example
     ///
@@ -870,20 +2480,23 @@ impl<K, V> IndexTreeMap<K, V> {
     /// use indextreemap::IndexTreeMap;
     ///
     /// let mut map = IndexTreeMap::new();
-    /// map.insert(1, "a");
-    ///
-    /// let first_value = map.values().next().unwrap();
-    /// assert_eq!(first_value, &"a");
+    /// map.insert(1, 10);
+    /// map.insert(2, 20);
+    ///
+    /// let mut cursor = map.cursor_back_mut();
+    /// if let Some((_, value)) = cursor.current_mut() {
+    ///     *value += 1;
+    /// }
+    /// drop(cursor);
+    /// assert_eq!(map.get(&2), Some(&21));
     /// ```
-    pub fn values(&self) -> IndexTreeValues<K, V> {
-        IndexTreeValues {
-            tree: self,
-            index: 0,
-        }
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, K, V> {
+        let index = self.size.saturating_sub(1);
+        CursorMut::new(self, index)
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
+impl<K: Ord, V> IndexTreeMap<K, V> {
     /// Removes an item from the map from its corresponding key, returning the key-value pair that was previously in the map.
     ///
     /// # Example
@@ -897,18 +2510,23 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     /// assert_eq!(tree.remove(&1), Some((1, "a".to_string())));
     /// assert_eq!(tree.remove(&2), None);
     /// ```
-    pub fn remove(&mut self, key: &K) -> Option<(K, V)> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         match self.root.remove(key) {
             None => None,
             Some(item) => {
                 self.size -= 1;
+                self.root.collapse_root();
                 Some((item.0, item.1))
             }
         }
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
+impl<K: Ord, V> IndexTreeMap<K, V> {
     /// Removes an item from the map from its corresponding index, returning the key-value pair that was previously in the map.
     ///
     /// # Example
@@ -927,13 +2545,115 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
             None => None,
             Some(item) => {
                 self.size -= 1;
+                self.root.collapse_root();
                 Some((item.0, item.1))
             }
         }
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
+impl<K, V> IndexTreeMap<K, V> {
+    /// Removes and returns the first key-value pair in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// assert_eq!(tree.pop_first(), Some((1, "a".to_string())));
+    /// assert_eq!(tree.pop_first(), Some((2, "b".to_string())));
+    /// assert_eq!(tree.pop_first(), None);
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let item = self.root.take_first_key()?;
+        self.size -= 1;
+        self.root.collapse_root();
+        Some((*item.key, *item.value))
+    }
+
+    /// Removes and returns the last key-value pair in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// assert_eq!(tree.pop_last(), Some((2, "b".to_string())));
+    /// assert_eq!(tree.pop_last(), Some((1, "a".to_string())));
+    /// assert_eq!(tree.pop_last(), None);
+    /// ```
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let item = self.root.take_last_key()?;
+        self.size -= 1;
+        self.root.collapse_root();
+        Some((*item.key, *item.value))
+    }
+
+    /// Returns the first entry in the map for in-place manipulation.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// if let Some(mut entry) = tree.first_entry() {
+    ///     entry.get_mut().push('!');
+    /// }
+    /// assert_eq!(tree.get(&1), Some(&"a!".to_string()));
+    /// ```
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (handle, _) = Handle::new(self, |node| match node.leaf {
+            true => Ok(0),
+            false => Err(0),
+        });
+        Some(OccupiedEntry { handle })
+    }
+
+    /// Returns the last entry in the map for in-place manipulation.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::new();
+    /// tree.insert(1, "a".to_string());
+    /// tree.insert(2, "b".to_string());
+    /// if let Some(entry) = tree.last_entry() {
+    ///     assert_eq!(entry.index(), 1);
+    ///     assert_eq!(entry.remove_entry(), (2, "b".to_string()));
+    /// }
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (handle, _) = Handle::new(self, |node| match node.leaf {
+            true => Ok(node.n - 1),
+            false => Err(node.n),
+        });
+        Some(OccupiedEntry { handle })
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
     /// Replaces an item from the map from it's corresponding key, returning the key-value pair was previously in the map.
     ///
     /// # Example
@@ -947,13 +2667,18 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     /// tree.replace(&1, "b".to_string());
     /// assert_eq!(tree.get(&1), Some(&"b".to_string()));
     /// ```
-    pub fn replace(&mut self, key: &K, value: V) -> Option<V> {
+    pub fn replace<Q>(&mut self, key: &Q, value: V) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.root.replace(key, value)
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
-    /// Replaces an item from the map from it's corresponding index, returning the key-value pair was previously in the map.
+impl<K, V> IndexTreeMap<K, V> {
+    /// Replaces the value at the given index, returning the value that was previously there,
+    /// or `None` if the index is out of bounds.
     ///
     /// # Example
     ///
@@ -963,18 +2688,19 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     ///
     /// let mut tree = IndexTreeMap::new();
     /// tree.insert(1, "a".to_string());
-    /// tree.replace_index(0, "b".to_string());
+    /// assert_eq!(tree.replace_index(0, "b".to_string()), Some("a".to_string()));
     /// assert_eq!(tree.get(&1), Some(&"b".to_string()));
+    /// assert_eq!(tree.replace_index(1, "c".to_string()), None);
     /// ```
-    pub fn replace_index(&mut self, index: usize, value: V) {
-        if self.contains_index(index) {
-            let key = self.get_key_from_index(index).unwrap();
-            self.root.insert(key.to_owned(), value);
+    pub fn replace_index(&mut self, index: usize, value: V) -> Option<V> {
+        if index < self.size {
+            return self.root.replace_from_index(index, value);
         }
+        None
     }
 }
 
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
+impl<K: Ord, V> IndexTreeMap<K, V> {
     /// Splits the map into two at the given key. Returns everything after the given key, including the key.
     ///
     /// # Example
@@ -995,38 +2721,17 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     /// assert_eq!(a.len(), 2);
     /// assert_eq!(b.len(), 3);
     /// ```
-    pub fn split_off(&mut self, key: &K) -> IndexTreeMap<K, V> {
-        if self.is_empty() {
-            return IndexTreeMap::new();
-        }
-
-        if let Some(pointer) = self.root.split_off(key) {
-            let size = pointer.counter;
-            self.root.n = self.root.keys.iter().filter(|item| item.is_some()).count();
-            let mut new_tree = IndexTreeMap {
-                root: pointer.child,
-                size,
-            };
-
-            self.root.fill_pointers();
-            new_tree.root.fill_pointers();
-
-            if self.root.is_empty() {
-                self.root.fill_empty_root();
-            }
-
-            if new_tree.root.is_empty() {
-                self.root.fill_empty_root()
-            }
-
-            self.size -= new_tree.size;
-
-            new_tree
-        } else {
-            IndexTreeMap::new()
-        }
+    pub fn split_off<Q>(&mut self, key: &Q) -> IndexTreeMap<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.lower_bound_index(key);
+        self.split_off_from_index(index)
     }
+}
 
+impl<K, V> IndexTreeMap<K, V> {
     /// Splits the map into two at the given index. Returns everything after the given key, including the key.
     ///
     /// # Example
@@ -1048,34 +2753,102 @@ impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
     /// assert_eq!(b.len(), 3);
     /// ```
     pub fn split_off_from_index(&mut self, index: usize) -> IndexTreeMap<K, V> {
-        if self.is_empty() {
-            return IndexTreeMap::new();
-        }
-
-        if let Some(pointer) = self.root.split_off_at_index(index) {
-            let size = pointer.counter;
-            self.root.n = self.root.keys.iter().filter(|item| item.is_some()).count();
-            let mut new_tree = IndexTreeMap {
-                root: pointer.child,
-                size,
-            };
-
-            self.root.fill_pointers();
-            new_tree.root.fill_pointers();
+        let index = index.min(self.size);
+        let mut right = IndexTreeMap {
+            root: self.root.split_off_at_index(index),
+            size: self.size - index,
+        };
+        self.size = index;
+
+        self.root.fix_right_border();
+        right.root.fix_left_border();
+        right
+    }
 
-            if self.root.is_empty() {
-                self.root.fill_empty_root();
-            }
+    /// Removes the entries at the given range of indexes.
+    ///
+    /// Whole subtrees inside the range are detached at once, so removing `k` entries takes `O(log n + k)` time.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::from_sorted_iter((0..10).map(|i| (i, i * 10)));
+    /// tree.remove_range(2..8);
+    /// assert!(tree.keys().copied().eq([0, 1, 8, 9]));
+    /// ```
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = index_range_from_bounds(&range, self.size);
+        self.take_range(start, end);
+    }
 
-            if new_tree.root.is_empty() {
-                self.root.fill_empty_root()
-            }
+    /// Removes the entries at the given range of indexes and returns them as an iterator in ascending key order.
+    ///
+    /// The entries are detached from the map before the iterator is returned, so they are removed even if
+    /// the iterator is dropped without being consumed.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::from_sorted_iter((0..10).map(|i| (i, i * 10)));
+    /// let drained: Vec<_> = tree.drain_range(..3).collect();
+    /// assert_eq!(drained, vec![(0, 0), (1, 10), (2, 20)]);
+    /// assert_eq!(tree.len(), 7);
+    /// ```
+    pub fn drain_range<R: RangeBounds<usize>>(&mut self, range: R) -> IndexTreeIntoIterator<K, V> {
+        let (start, end) = index_range_from_bounds(&range, self.size);
+        self.take_range(start, end).into_iter()
+    }
+}
 
-            self.size -= new_tree.size;
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    /// Removes the entries whose keys fall in the given range.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::from_sorted_iter((0..10).map(|i| (i, i * 10)));
+    /// tree.remove_key_range(3..=6);
+    /// assert!(tree.keys().copied().eq([0, 1, 2, 7, 8, 9]));
+    /// ```
+    pub fn remove_key_range<Q, R>(&mut self, range: R)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.index_range_from_key_range(&range);
+        self.take_range(start, end);
+    }
 
-            new_tree
-        } else {
-            IndexTreeMap::new()
-        }
+    /// Removes the entries whose keys fall in the given range and returns them as an iterator in ascending key order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut tree = IndexTreeMap::from_sorted_iter((0..10).map(|i| (i, i * 10)));
+    /// let drained: Vec<_> = tree.drain_key_range(7..).collect();
+    /// assert_eq!(drained, vec![(7, 70), (8, 80), (9, 90)]);
+    /// assert_eq!(tree.len(), 7);
+    /// ```
+    pub fn drain_key_range<Q, R>(&mut self, range: R) -> IndexTreeIntoIterator<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = self.index_range_from_key_range(&range);
+        self.take_range(start, end).into_iter()
     }
 }
@@ -0,0 +1,121 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{
+    stc::{Item, Node, Pointer},
+    IndexTreeMap, KEY_ARRAY,
+};
+
+/// The error returned by [`IndexTreeMap::try_from_sorted_iter`] and [`crate::IndexTreeSet::try_from_sorted_iter`]
+/// when a key is not strictly greater than the key before it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnsortedKeyError {
+    /// The position in the input of the first out-of-order or duplicate key.
+    pub index: usize,
+}
+
+impl Display for UnsortedKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key at position {} is not greater than the key before it",
+            self.index
+        )
+    }
+}
+
+impl Error for UnsortedKeyError {}
+
+impl<K, V> Node<K, V> {
+    // builds a subtree holding the next `count` items, which must already be in ascending key order.
+    // items are spread evenly over the fewest levels that fit them, so every node but the root
+    // ends up with at least half of KEY_ARRAY keys and all leaves sit at the same depth.
+    pub fn from_sorted_items<I>(items: &mut I, count: usize) -> Box<Node<K, V>>
+    where
+        I: Iterator<Item = Box<Item<K, V>>>,
+    {
+        let mut node = Node::new();
+
+        // the number of items a full subtree of one level less than this node can hold
+        let mut child_capacity = 0;
+        let mut capacity = KEY_ARRAY - 1;
+        while capacity < count {
+            child_capacity = capacity;
+            capacity = capacity
+                .saturating_mul(KEY_ARRAY)
+                .saturating_add(KEY_ARRAY - 1);
+        }
+
+        if child_capacity == 0 {
+            for index in 0..count {
+                node.keys[index] = items.next();
+            }
+            node.n = count;
+            return node;
+        }
+
+        let children = (count + 1).div_ceil(child_capacity + 1);
+        let remaining = count - (children - 1);
+        for index in 0..children {
+            let size = remaining / children + usize::from(index < remaining % children);
+            node.pointers[index] = Some(Pointer {
+                child: Node::from_sorted_items(items, size),
+                counter: size,
+            });
+            if index + 1 < children {
+                node.keys[index] = items.next();
+            }
+        }
+        node.n = children - 1;
+        node.leaf = false;
+        node
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    // builds a map from items that are already in strictly ascending key order
+    pub(crate) fn from_sorted_items<I>(mut items: I) -> IndexTreeMap<K, V>
+    where
+        I: ExactSizeIterator<Item = Box<Item<K, V>>>,
+    {
+        let size = items.len();
+        IndexTreeMap {
+            root: Node::from_sorted_items(&mut items, size),
+            size,
+        }
+    }
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    // builds a map from pairs in any order in one pass; like repeated inserts,
+    // a duplicate key keeps its first occurrence and takes the value of its last one
+    pub(crate) fn from_unsorted_vec(mut pairs: Vec<(K, V)>) -> IndexTreeMap<K, V> {
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut items: Vec<Box<Item<K, V>>> = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            match items.last_mut() {
+                Some(last) if *last.key == key => *last.value = value,
+                _ => items.push(Box::new(Item::new(key, value))),
+            }
+        }
+        IndexTreeMap::from_sorted_items(items.into_iter())
+    }
+
+    // like `from_sorted_items`, but fails on the first key that does not follow its predecessor
+    pub(crate) fn try_from_sorted_pairs<I>(pairs: I) -> Result<IndexTreeMap<K, V>, UnsortedKeyError>
+    where
+        I: Iterator<Item = (K, V)>,
+    {
+        let mut items: Vec<Box<Item<K, V>>> = Vec::with_capacity(pairs.size_hint().0);
+        for (index, (key, value)) in pairs.enumerate() {
+            if items.last().is_some_and(|last| *last.key >= key) {
+                return Err(UnsortedKeyError { index });
+            }
+            items.push(Box::new(Item::new(key, value)));
+        }
+        Ok(IndexTreeMap::from_sorted_items(items.into_iter()))
+    }
+}
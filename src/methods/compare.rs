@@ -0,0 +1,38 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::IndexTreeMap;
+
+// comparisons walk both maps in key order, so maps with equal contents compare equal
+// whatever shape their nodes were left in
+
+impl<K: PartialEq, V: PartialEq> PartialEq for IndexTreeMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for IndexTreeMap<K, V> {}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for IndexTreeMap<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for IndexTreeMap<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for IndexTreeMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.size);
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
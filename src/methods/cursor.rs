@@ -0,0 +1,913 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering::{Equal, Greater},
+    error::Error,
+    fmt::{self, Debug, Display},
+    mem,
+    ops::Deref,
+};
+
+use crate::{
+    stc::{
+        Item, Node,
+        Output::{NewKeyPointer, Null},
+        Pointer,
+    },
+    IndexTreeMap, MIN_KEYS,
+};
+
+//Cursor
+// points at an entry by its index and holds the path from the root down to the node of that entry;
+// an index equal to the length of the map is the "ghost" position, which sits between the last
+// and the first entry and keeps the path at the root
+pub struct Cursor<'a, K, V> {
+    pub(crate) tree: &'a IndexTreeMap<K, V>,
+    pub(crate) index: usize,
+    path: Path<&'a Node<K, V>>,
+}
+
+//Cursor Mut
+// like `Cursor`, but the nodes on its path are taken out of the tree while the cursor lives,
+// so that entries can be inserted and removed where it points; they are put back on drop.
+// the map is left empty until then, so a leaked cursor loses the entries but not the map's consistency
+pub struct CursorMut<'a, K, V> {
+    pub(crate) tree: &'a mut IndexTreeMap<K, V>,
+    pub(crate) index: usize,
+    pub(crate) size: usize,
+    path: Path<Box<Node<K, V>>>,
+}
+
+/// The error returned by [`CursorMut::insert_before`] and [`CursorMut::insert_after`]
+/// when the key would not sit strictly between the neighbouring keys.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnorderedKeyError {}
+
+impl Display for UnorderedKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key is not properly ordered relative to neighbors")
+    }
+}
+
+impl Error for UnorderedKeyError {}
+
+impl<'a, K, V> Clone for Cursor<'a, K, V> {
+    fn clone(&self) -> Self {
+        Cursor {
+            tree: self.tree,
+            index: self.index,
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for Cursor<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("index", &self.index())
+            .field("current", &self.current())
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for CursorMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CursorMut")
+            .field("index", &self.index())
+            .field("current", &self.current())
+            .finish()
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    pub(crate) fn new(tree: &'a IndexTreeMap<K, V>, index: usize) -> Cursor<'a, K, V> {
+        let mut cursor = Cursor {
+            tree,
+            index: index.min(tree.size),
+            path: Path::new(&tree.root),
+        };
+        cursor.settle();
+        cursor
+    }
+
+    /// Returns the index of the current entry, or `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// assert_eq!(cursor.index(), Some(0));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.index(), None);
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.tree.size).then_some(self.index)
+    }
+
+    /// Returns the current entry, or `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back();
+    /// assert_eq!(cursor.current(), Some((&3, &"c")));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), None);
+    /// ```
+    pub fn current(&self) -> Option<(&'a K, &'a V)> {
+        if self.index < self.tree.size {
+            return self.path.node.keys[self.path.slot].as_deref().map(pair);
+        }
+        None
+    }
+
+    /// Returns the entry after the current one, wrapping from the ghost position to the first entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back();
+    /// assert_eq!(cursor.peek_next(), None);
+    /// cursor.move_next();
+    /// assert_eq!(cursor.peek_next(), Some((&1, &"a")));
+    /// ```
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let item = match self.index < self.tree.size {
+            true => next_item(self.path.node, self.path.slot, self.frames()),
+            false => first_item(self.path.node),
+        };
+        item.map(pair)
+    }
+
+    /// Returns the entry before the current one, wrapping from the ghost position to the last entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// assert_eq!(cursor.peek_prev(), None);
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.peek_prev(), Some((&3, &"c")));
+    /// ```
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let item = match self.index < self.tree.size {
+            true => prev_item(self.path.node, self.path.slot, self.frames()),
+            false => last_item(self.path.node),
+        };
+        item.map(pair)
+    }
+
+    /// Moves the cursor to the next entry. Past the last entry it moves to the ghost position,
+    /// and from the ghost position it moves to the first entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back();
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), None);
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some((&1, &"a")));
+    /// ```
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.tree.size);
+        self.settle();
+    }
+
+    /// Moves the cursor to the previous entry. Before the first entry it moves to the ghost position,
+    /// and from the ghost position it moves to the last entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), None);
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), Some((&3, &"c")));
+    /// ```
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.tree.size);
+        self.settle();
+    }
+
+    /// Moves the cursor to the entry at the given index, or to the ghost position if the index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// cursor.seek_index(1);
+    /// assert_eq!(cursor.current(), Some((&2, &"b")));
+    /// cursor.seek_index(10);
+    /// assert_eq!(cursor.index(), None);
+    /// ```
+    pub fn seek_index(&mut self, index: usize) {
+        self.index = index.min(self.tree.size);
+        self.settle();
+    }
+
+    fn frames(&self) -> impl DoubleEndedIterator<Item = (&'a Node<K, V>, usize)> + '_ {
+        self.path
+            .frames
+            .iter()
+            .map(|frame| (frame.node, frame.slot))
+    }
+
+    fn settle(&mut self) {
+        match self.index < self.tree.size {
+            true => self.path.seek(self.index),
+            false => self.path.top(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the given key, or to the next greater key if it is absent,
+    /// or to the ghost position if there is no greater key. Returns whether the key was found.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map = IndexTreeMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front();
+    /// assert!(cursor.seek_key(&20));
+    /// assert_eq!(cursor.index(), Some(1));
+    ///
+    /// // an absent key leaves the cursor on the next greater key
+    /// assert!(!cursor.seek_key(&25));
+    /// assert_eq!(cursor.current(), Some((&30, &"c")));
+    ///
+    /// // and past the last key, on the ghost position
+    /// assert!(!cursor.seek_key(&35));
+    /// assert_eq!(cursor.index(), None);
+    /// ```
+    pub fn seek_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (index, found) = self.path.seek_key(key);
+        self.index = index;
+        self.settle();
+        found
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    pub(crate) fn new(tree: &'a mut IndexTreeMap<K, V>, index: usize) -> CursorMut<'a, K, V> {
+        let root = mem::take(&mut tree.root);
+        let size = mem::take(&mut tree.size);
+        let mut cursor = CursorMut {
+            index: index.min(size),
+            size,
+            tree,
+            path: Path::new(root),
+        };
+        cursor.settle();
+        cursor
+    }
+
+    /// Returns the index of the current entry, or `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back_mut();
+    /// assert_eq!(cursor.index(), Some(2));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.index(), None);
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.size).then_some(self.index)
+    }
+
+    /// Returns the current entry, or `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// assert_eq!(cursor.current(), Some((&1, &"a")));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), None);
+    /// ```
+    pub fn current(&self) -> Option<(&K, &V)> {
+        if self.index < self.size {
+            return self.path.node.keys[self.path.slot].as_deref().map(pair);
+        }
+        None
+    }
+
+    /// Returns the current entry with a mutable value, or `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, 10), (2, 20), (3, 30)]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// cursor.seek_index(1);
+    /// if let Some((_, value)) = cursor.current_mut() {
+    ///     *value += 1;
+    /// }
+    ///
+    /// // the ghost position has no entry to change
+    /// cursor.seek_index(3);
+    /// assert_eq!(cursor.current_mut(), None);
+    ///
+    /// drop(cursor);
+    /// assert_eq!(map.get(&2), Some(&21));
+    /// ```
+    pub fn current_mut(&mut self) -> Option<(&K, &mut V)> {
+        if self.index < self.size {
+            let Item { key, value } = self.path.node.keys[self.path.slot].as_deref_mut()?;
+            return Some((key, value));
+        }
+        None
+    }
+
+    /// Returns the entry after the current one, wrapping from the ghost position to the first entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    /// cursor.seek_index(2);
+    /// assert_eq!(cursor.peek_next(), None);
+    /// ```
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        let item = match self.index < self.size {
+            true => next_item(&self.path.node, self.path.slot, self.frames()),
+            false => first_item(&self.path.node),
+        };
+        item.map(pair)
+    }
+
+    /// Returns the entry before the current one, wrapping from the ghost position to the last entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back_mut();
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// cursor.seek_index(0);
+    /// assert_eq!(cursor.peek_prev(), None);
+    /// ```
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        let item = match self.index < self.size {
+            true => prev_item(&self.path.node, self.path.slot, self.frames()),
+            false => last_item(&self.path.node),
+        };
+        item.map(pair)
+    }
+
+    /// Moves the cursor to the next entry. Past the last entry it moves to the ghost position,
+    /// and from the ghost position it moves to the first entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some((&2, &"b")));
+    /// ```
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.size);
+        self.settle();
+    }
+
+    /// Moves the cursor to the previous entry. Before the first entry it moves to the ghost position,
+    /// and from the ghost position it moves to the last entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back_mut();
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), Some((&2, &"b")));
+    /// ```
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.size);
+        self.settle();
+    }
+
+    /// Moves the cursor to the entry at the given index, or to the ghost position if the index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// cursor.seek_index(2);
+    /// assert_eq!(cursor.current(), Some((&3, &"c")));
+    /// ```
+    pub fn seek_index(&mut self, index: usize) {
+        self.index = index.min(self.size);
+        self.settle();
+    }
+
+    /// Removes the current entry and returns it, moving the cursor to the next entry.
+    /// Returns `None` if the cursor is at the ghost position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// cursor.seek_index(1);
+    /// assert_eq!(cursor.remove_current(), Some((2, "b")));
+    ///
+    /// // the cursor moves on to the entry that followed, which now has the removed entry's index
+    /// assert_eq!(cursor.current(), Some((&3, &"c")));
+    /// assert_eq!(cursor.index(), Some(1));
+    ///
+    /// // removing the last entry leaves the cursor at the ghost position, where there is nothing to remove
+    /// assert_eq!(cursor.remove_current(), Some((3, "c")));
+    /// assert_eq!(cursor.index(), None);
+    /// assert_eq!(cursor.remove_current(), None);
+    ///
+    /// drop(cursor);
+    /// assert!(map.keys().eq([1].iter()));
+    /// ```
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        if self.index >= self.size {
+            return None;
+        }
+        let item = self.path.node.remove_key(self.path.slot)?;
+        self.size -= 1;
+
+        // a node left short of keys is topped up by its parent, which may run short in turn
+        while self.path.node.n < MIN_KEYS {
+            let Some(slot) = self.path.up() else {
+                break;
+            };
+            self.path.node.rebalance_child(slot);
+        }
+        if self.path.frames.is_empty() {
+            self.path.node.collapse_root();
+        }
+        self.settle();
+        Some((*item.key, *item.value))
+    }
+
+    // inserts a new entry that will sit at `index`, splitting the nodes on the path that overflow
+    fn insert_at(&mut self, index: usize, key: K, value: V) {
+        let size = self.size;
+        if index < size {
+            self.path.seek(index);
+            if !self.path.node.leaf {
+                // the gap before an internal key is at the end of the last leaf of its left subtree
+                self.path.down(self.path.slot);
+                while !self.path.node.leaf {
+                    self.path.down(self.path.node.n);
+                }
+                self.path.slot = self.path.node.n;
+            }
+        } else if size > 0 {
+            // the last entry always sits in a leaf
+            self.path.seek(size - 1);
+            self.path.slot += 1;
+        } else {
+            self.path.top();
+            self.path.slot = 0;
+        }
+        self.path.node.insert_to_keys(self.path.slot, key, value);
+        self.size += 1;
+
+        let mut output = match self.path.node.is_full() {
+            true => self.path.node.split_leaf(),
+            false => Null,
+        };
+        while let NewKeyPointer(key, pointer) = output {
+            output = match self.path.up() {
+                Some(slot) => self.path.node.insert_key_pointer(slot, key, pointer),
+                None => {
+                    self.path.node.update_root(key, pointer);
+                    Null
+                }
+            };
+        }
+    }
+
+    fn frames(&self) -> impl DoubleEndedIterator<Item = (&Node<K, V>, usize)> + '_ {
+        self.path
+            .frames
+            .iter()
+            .map(|frame| (&*frame.node, frame.slot))
+    }
+
+    fn settle(&mut self) {
+        match self.index < self.size {
+            true => self.path.seek(self.index),
+            false => self.path.top(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    /// Moves the cursor to the given key, or to the next greater key if it is absent,
+    /// or to the ghost position if there is no greater key. Returns whether the key was found.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// assert!(!cursor.seek_key(&15));
+    /// assert_eq!(cursor.current(), Some((&20, &"b")));
+    ///
+    /// // the gap left by the absent key is right before the cursor
+    /// assert!(cursor.insert_before(15, "x").is_ok());
+    /// drop(cursor);
+    /// assert!(map.keys().eq([10, 15, 20, 30].iter()));
+    /// ```
+    pub fn seek_key<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (index, found) = self.path.seek_key(key);
+        self.index = index;
+        self.settle();
+        found
+    }
+
+    /// Inserts a new entry before the current one, or at the end of the map if the cursor is at the ghost position.
+    /// The cursor keeps pointing at the same entry.
+    ///
+    /// Returns an error, leaving the map unchanged, if the key is not strictly between the previous and current keys.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_back_mut();
+    /// assert!(cursor.insert_before(2, "x").is_err());
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    ///
+    /// cursor.move_next();
+    /// assert!(cursor.insert_before(4, "d").is_ok());
+    /// assert_eq!(cursor.index(), None);
+    /// drop(cursor);
+    /// assert!(map.keys().eq([1, 2, 3, 4].iter()));
+    /// ```
+    pub fn insert_before(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
+        let prev = self.peek_prev();
+        let next = self.current();
+        if prev.is_some_and(|(prev, _)| *prev >= key) || next.is_some_and(|(next, _)| *next <= key)
+        {
+            return Err(UnorderedKeyError {});
+        }
+        self.insert_at(self.index, key, value);
+        self.index += 1;
+        self.settle();
+        Ok(())
+    }
+
+    /// Inserts a new entry after the current one, or at the start of the map if the cursor is at the ghost position.
+    /// The cursor keeps pointing at the same entry.
+    ///
+    /// Returns an error, leaving the map unchanged, if the key is not strictly between the current and next keys.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let mut cursor = map.cursor_front_mut();
+    /// assert!(cursor.insert_after(3, "x").is_err());
+    ///
+    /// // from the ghost position the entry goes in front of the first one
+    /// cursor.move_prev();
+    /// assert!(cursor.insert_after(0, "z").is_ok());
+    /// assert_eq!(cursor.index(), None);
+    /// drop(cursor);
+    /// assert!(map.keys().eq([0, 1, 2, 3].iter()));
+    /// ```
+    pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
+        let ghost = self.index >= self.size;
+        let prev = self.current();
+        let next = self.peek_next();
+        if prev.is_some_and(|(prev, _)| *prev >= key) || next.is_some_and(|(next, _)| *next <= key)
+        {
+            return Err(UnorderedKeyError {});
+        }
+        match ghost {
+            true => {
+                self.insert_at(0, key, value);
+                self.index += 1;
+            }
+            false => self.insert_at(self.index + 1, key, value),
+        }
+        self.settle();
+        Ok(())
+    }
+}
+
+impl<'a, K, V> Drop for CursorMut<'a, K, V> {
+    fn drop(&mut self) {
+        self.path.top();
+        self.tree.root = mem::take(&mut self.path.node);
+        self.tree.size = self.size;
+    }
+}
+
+// a node on the path of a cursor, either borrowed from the tree or taken out of it
+trait Hold: Sized {
+    // takes the child at `loc` onto the path
+    fn take_child(&mut self, loc: usize) -> Self;
+
+    // gives back the child taken from `loc`
+    fn put_child(&mut self, loc: usize, child: Self);
+}
+
+impl<'a, K, V> Hold for &'a Node<K, V> {
+    fn take_child(&mut self, loc: usize) -> Self {
+        let node: &'a Node<K, V> = self;
+        &node.pointers[loc].as_ref().unwrap().child
+    }
+
+    fn put_child(&mut self, _: usize, _: Self) {}
+}
+
+impl<K, V> Hold for Box<Node<K, V>> {
+    fn take_child(&mut self, loc: usize) -> Self {
+        self.pointers[loc].take().unwrap().child
+    }
+
+    fn put_child(&mut self, loc: usize, child: Self) {
+        let counter = child.size();
+        self.pointers[loc] = Some(Pointer { child, counter });
+    }
+}
+
+// a node above the cursor, the slot of its child that the path goes through,
+// and the index of the first entry below it
+#[derive(Clone)]
+struct Frame<N> {
+    node: N,
+    slot: usize,
+    start: usize,
+}
+
+// the nodes from the root down to the node holding the entry the cursor points at,
+// which is the key at `slot` of `node`; `start` is the index of the first entry below `node`
+#[derive(Clone)]
+struct Path<N> {
+    frames: Vec<Frame<N>>,
+    node: N,
+    start: usize,
+    slot: usize,
+}
+
+impl<K, V, N: Hold + Deref<Target = Node<K, V>>> Path<N> {
+    fn new(root: N) -> Path<N> {
+        Path {
+            frames: Vec::new(),
+            node: root,
+            start: 0,
+            slot: 0,
+        }
+    }
+
+    // moves up to the parent, returning the slot of the child it came from
+    fn up(&mut self) -> Option<usize> {
+        let frame = self.frames.pop()?;
+        let child = mem::replace(&mut self.node, frame.node);
+        self.node.put_child(frame.slot, child);
+        self.start = frame.start;
+        Some(frame.slot)
+    }
+
+    // moves down to the child at `loc`
+    fn down(&mut self, loc: usize) {
+        let start = self.start + self.node.rank_before(loc);
+        let child = self.node.take_child(loc);
+        let node = mem::replace(&mut self.node, child);
+        self.frames.push(Frame {
+            node,
+            slot: loc,
+            start: self.start,
+        });
+        self.start = start;
+    }
+
+    fn top(&mut self) {
+        while self.up().is_some() {}
+    }
+
+    // moves to the entry at `index`, which must be in the tree, climbing only as far as the
+    // subtree that holds it
+    fn seek(&mut self, index: usize) {
+        while !(self.start..self.start + self.node.size()).contains(&index) && self.up().is_some() {
+        }
+        loop {
+            let mut offset = index - self.start;
+            if self.node.leaf {
+                self.slot = offset;
+                return;
+            }
+            let mut loc = 0;
+            loop {
+                let counter = self.node.pointers[loc].as_ref().map_or(0, |p| p.counter);
+                if offset < counter {
+                    break;
+                }
+                offset -= counter;
+                if offset == 0 {
+                    self.slot = loc;
+                    return;
+                }
+                offset -= 1;
+                loc += 1;
+            }
+            self.down(loc);
+        }
+    }
+
+    // moves down from the root towards `key`, returning the index of the first entry whose key
+    // is not less than it and whether that key is equal
+    fn seek_key<Q>(&mut self, key: &Q) -> (usize, bool)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.top();
+        loop {
+            let node = &*self.node;
+            let mut slot = 0;
+            while slot < node.n {
+                match node.keys[slot]
+                    .as_ref()
+                    .map(|item| key.cmp((*item.key).borrow()))
+                {
+                    Some(Greater) => slot += 1,
+                    Some(Equal) => return (self.start + node.rank_before(slot + 1) - 1, true),
+                    _ => break,
+                }
+            }
+            if node.leaf {
+                return (self.start + slot, false);
+            }
+            self.down(slot);
+        }
+    }
+}
+
+// the entry after the key at `slot` of `node`, looking through the nodes above it, nearest last
+fn next_item<'b, K, V>(
+    node: &'b Node<K, V>,
+    slot: usize,
+    frames: impl DoubleEndedIterator<Item = (&'b Node<K, V>, usize)>,
+) -> Option<&'b Item<K, V>> {
+    if !node.leaf {
+        return first_item(&node.pointers[slot + 1].as_ref()?.child);
+    }
+    if slot + 1 < node.n {
+        return node.keys[slot + 1].as_deref();
+    }
+    let (node, slot) = frames.rev().find(|(node, slot)| *slot < node.n)?;
+    node.keys[slot].as_deref()
+}
+
+// the entry before the key at `slot` of `node`, looking through the nodes above it, nearest last
+fn prev_item<'b, K, V>(
+    node: &'b Node<K, V>,
+    slot: usize,
+    frames: impl DoubleEndedIterator<Item = (&'b Node<K, V>, usize)>,
+) -> Option<&'b Item<K, V>> {
+    if !node.leaf {
+        return last_item(&node.pointers[slot].as_ref()?.child);
+    }
+    if slot > 0 {
+        return node.keys[slot - 1].as_deref();
+    }
+    let (node, slot) = frames.rev().find(|(_, slot)| *slot > 0)?;
+    node.keys[slot - 1].as_deref()
+}
+
+fn first_item<K, V>(mut node: &Node<K, V>) -> Option<&Item<K, V>> {
+    while !node.leaf {
+        node = &node.pointers[0].as_ref()?.child;
+    }
+    node.keys[0].as_deref()
+}
+
+fn last_item<K, V>(mut node: &Node<K, V>) -> Option<&Item<K, V>> {
+    while !node.leaf {
+        node = &node.pointers[node.n].as_ref()?.child;
+    }
+    node.keys[node.n.checked_sub(1)?].as_deref()
+}
+
+fn pair<K, V>(item: &Item<K, V>) -> (&K, &V) {
+    (&item.key, &item.value)
+}
+
+fn next_index(index: usize, size: usize) -> usize {
+    if index >= size {
+        0
+    } else {
+        index + 1
+    }
+}
+
+fn prev_index(index: usize, size: usize) -> usize {
+    if index == 0 {
+        size
+    } else {
+        index - 1
+    }
+}
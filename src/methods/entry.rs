@@ -0,0 +1,640 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    mem,
+};
+
+use crate::{
+    stc::{Item, Node, Pointer},
+    IndexTreeMap, KEY_ARRAY, MIN_KEYS,
+};
+
+//Entry
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    pub(crate) handle: Handle<'a, K, V>,
+}
+
+pub struct VacantEntry<'a, K, V> {
+    pub(crate) handle: Handle<'a, K, V>,
+    pub(crate) key: K,
+}
+
+// the node an entry lives in (or, for a vacant entry, the leaf it will go into) and the slot in it,
+// along with the counters of the pointers leading down to it and the size of the map,
+// so that the entry can be read, inserted or removed without another descent
+pub(crate) struct Handle<'a, K, V> {
+    node: &'a mut Node<K, V>,
+    slot: usize,
+    counters: Vec<&'a mut usize>,
+    size: &'a mut usize,
+    index: usize,
+}
+
+/// The error returned by [`IndexTreeMap::try_insert`] when the key is already present.
+///
+/// Contains the occupied entry and the value that was not inserted.
+///
+/// # Example
+///
+/// Basic usage:
+/// ```rust
+/// use indextreemap::IndexTreeMap;
+///
+/// let mut map = IndexTreeMap::from([(1, "a"), (2, "b")]);
+///
+/// let err = map.try_insert(2, "x").unwrap_err();
+/// assert_eq!(err.entry.index(), 1);
+/// assert_eq!(err.entry.get(), &"b");
+/// assert_eq!(err.value, "x");
+/// assert_eq!(
+///     err.to_string(),
+///     r#"failed to insert "x", key 2 already exists at index 1 with value "b""#
+/// );
+///
+/// // the entry can still be used to change the map
+/// err.entry.remove();
+/// assert_eq!(map.try_insert(2, "x").unwrap(), (1, &mut "x"));
+/// ```
+pub struct OccupiedError<'a, K, V> {
+    /// The entry in the map that was already occupied.
+    pub entry: OccupiedEntry<'a, K, V>,
+    /// The value which was not inserted, because the entry was already occupied.
+    pub value: V,
+}
+
+impl<'a, K: Debug, V: Debug> Debug for Entry<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Occupied(entry) => f.debug_tuple("Entry").field(entry).finish(),
+            Entry::Vacant(entry) => f.debug_tuple("Entry").field(entry).finish(),
+        }
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for OccupiedEntry<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntry")
+            .field("index", &self.index())
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V> Debug for VacantEntry<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VacantEntry")
+            .field("index", &self.index())
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("index", &self.entry.index())
+            .field("key", self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Display for OccupiedError<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists at index {} with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.index(),
+            self.entry.get(),
+        )
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Error for OccupiedError<'a, K, V> {}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// assert_eq!(map.entry("b").key(), &"b");
+    /// ```
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Returns the index of the entry, or the index it will have once inserted.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// // an occupied entry reports where its key is
+    /// assert_eq!(map.entry("c").index(), 1);
+    ///
+    /// // a vacant one reports where its key will go
+    /// assert_eq!(map.entry("b").index(), 1);
+    /// assert_eq!(map.entry("d").index(), 2);
+    /// ```
+    pub fn index(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.index(),
+            Entry::Vacant(entry) => entry.index(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// map.entry("a").and_modify(|v| *v += 10).or_insert(0);
+    /// map.entry("b").and_modify(|v| *v += 10).or_insert(0);
+    /// assert_eq!(map.get(&"a"), Some(&11));
+    /// assert_eq!(map.get(&"b"), Some(&0));
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// *map.entry("a").or_insert(10) += 1;
+    /// *map.entry("b").or_insert(10) += 1;
+    /// assert_eq!(map.get(&"a"), Some(&2));
+    /// assert_eq!(map.get(&"b"), Some(&11));
+    /// ```
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// let value = map.entry("b").or_insert_with(|| 2);
+    /// assert_eq!(value, &2);
+    /// assert_eq!(map.get_index_from_key(&"b"), Some(1));
+    /// ```
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function called with the key if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map: IndexTreeMap<&str, usize> = IndexTreeMap::new();
+    ///
+    /// assert_eq!(map.entry("four").or_insert_with_key(|key| key.len()), &4);
+    /// ```
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+}
+
+impl<'a, K, V: Default> Entry<'a, K, V> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map: IndexTreeMap<&str, Vec<i32>> = IndexTreeMap::new();
+    ///
+    /// map.entry("a").or_default().push(1);
+    /// map.entry("a").or_default().push(2);
+    /// assert_eq!(map.get(&"a"), Some(&vec![1, 2]));
+    /// ```
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(Default::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Gets a reference to the key in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(entry) = map.entry("c") {
+    ///     assert_eq!(entry.key(), &"c");
+    /// }
+    /// ```
+    pub fn key(&self) -> &K {
+        &self.handle.item().key
+    }
+
+    /// Returns the index of the entry in the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(entry) = map.entry("c") {
+    ///     assert_eq!(entry.index(), 1);
+    /// }
+    /// ```
+    pub fn index(&self) -> usize {
+        self.handle.index()
+    }
+
+    /// Gets a reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(entry) = map.entry("c") {
+    ///     assert_eq!(entry.get(), &3);
+    /// }
+    /// ```
+    pub fn get(&self) -> &V {
+        &self.handle.item().value
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(mut entry) = map.entry("c") {
+    ///     *entry.get_mut() += 10;
+    ///     *entry.get_mut() += 10;
+    /// }
+    /// assert_eq!(map.get(&"c"), Some(&23));
+    /// ```
+    pub fn get_mut(&mut self) -> &mut V {
+        let slot = self.handle.slot;
+        self.handle.node.keys[slot].as_mut().unwrap().value.as_mut()
+    }
+
+    /// Converts the entry into a mutable reference to its value, with the lifetime of the map.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// let value = match map.entry("c") {
+    ///     Entry::Occupied(entry) => entry.into_mut(),
+    ///     Entry::Vacant(entry) => entry.insert(0),
+    /// };
+    /// *value += 10;
+    /// assert_eq!(map.get(&"c"), Some(&13));
+    /// ```
+    pub fn into_mut(self) -> &'a mut V {
+        let Handle { node, slot, .. } = self.handle;
+        node.keys[slot].as_mut().unwrap().value.as_mut()
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(mut entry) = map.entry("c") {
+    ///     assert_eq!(entry.insert(30), 3);
+    /// }
+    /// assert_eq!(map.get(&"c"), Some(&30));
+    /// ```
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the key-value pair out of the map, and returns it.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(entry) = map.entry("a") {
+    ///     assert_eq!(entry.remove_entry(), ("a", 1));
+    /// }
+    /// assert_eq!(map.get_index_from_key(&"c"), Some(0));
+    /// ```
+    pub fn remove_entry(self) -> (K, V) {
+        let Handle {
+            node,
+            slot,
+            counters,
+            size,
+            ..
+        } = self.handle;
+        let item = node.remove_key(slot).unwrap();
+        if counters.is_empty() {
+            node.collapse_root();
+        }
+        for counter in counters {
+            *counter -= 1;
+        }
+        *size -= 1;
+        (*item.key, *item.value)
+    }
+
+    /// Takes the value of the entry out of the map, and returns it.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Occupied(entry) = map.entry("a") {
+    ///     assert_eq!(entry.remove(), 1);
+    /// }
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    /// Gets a reference to the key that would be used when inserting a value through the VacantEntry.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Vacant(entry) = map.entry("b") {
+    ///     assert_eq!(entry.key(), &"b");
+    /// }
+    /// ```
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Vacant(entry) = map.entry("b") {
+    ///     assert_eq!(entry.into_key(), "b");
+    /// }
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Returns the index the entry will have once inserted.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Vacant(entry) = map.entry("b") {
+    ///     assert_eq!(entry.index(), 1);
+    /// }
+    /// ```
+    pub fn index(&self) -> usize {
+        self.handle.index()
+    }
+
+    /// Sets the value of the entry with the VacantEntry's key, and returns a mutable reference to it.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::methods::entry::Entry;
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::from([("a", 1), ("c", 3)]);
+    ///
+    /// if let Entry::Vacant(entry) = map.entry("b") {
+    ///     *entry.insert(2) += 10;
+    /// }
+    /// assert_eq!(map.get(&"b"), Some(&12));
+    /// assert_eq!(map.get_index_from_key(&"b"), Some(1));
+    /// ```
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.handle.insert(self.key, value)
+    }
+}
+
+impl<'a, K, V> Handle<'a, K, V> {
+    // walks down from the root, taking the slot that `step` picks in each node: Ok for a key of
+    // the node, Err for the child (or, in a leaf, the gap) before that slot. Full children are
+    // split and thin ones topped up before the walk enters them, so the node it stops at can
+    // gain or lose a key without its ancestors having to change shape.
+    // Returns the handle, and whether it points at a key.
+    pub(crate) fn new<F>(tree: &'a mut IndexTreeMap<K, V>, mut step: F) -> (Handle<'a, K, V>, bool)
+    where
+        F: FnMut(&Node<K, V>) -> Result<usize, usize>,
+    {
+        let IndexTreeMap { root, size } = tree;
+        if root.n >= KEY_ARRAY - 1 {
+            let mut new_root = Node::new();
+            new_root.leaf = false;
+            new_root.pointers[0] = Some(Pointer {
+                child: mem::take(root),
+                counter: *size,
+            });
+            new_root.split_child(0);
+            *root = new_root;
+        }
+
+        let mut node: &'a mut Node<K, V> = root;
+        let mut counters = Vec::new();
+        let mut index = 0;
+        loop {
+            let loc = match step(node) {
+                Ok(slot) => {
+                    index += node.rank_before(slot + 1) - 1;
+                    let handle = Handle {
+                        node,
+                        slot,
+                        counters,
+                        size,
+                        index,
+                    };
+                    return (handle, true);
+                }
+                Err(slot) if node.leaf => {
+                    index += slot;
+                    let handle = Handle {
+                        node,
+                        slot,
+                        counters,
+                        size,
+                        index,
+                    };
+                    return (handle, false);
+                }
+                Err(loc) => loc,
+            };
+
+            let n = node.pointers[loc].as_ref().map_or(0, |p| p.child.n);
+            if n >= KEY_ARRAY - 1 {
+                node.split_child(loc);
+                continue;
+            }
+            if n <= MIN_KEYS {
+                node.top_up_child(loc);
+                if counters.is_empty() {
+                    node.collapse_root();
+                }
+                continue;
+            }
+
+            index += node.rank_before(loc);
+            let Pointer { child, counter } = node.pointers[loc].as_mut().unwrap();
+            counters.push(counter);
+            node = child;
+        }
+    }
+
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    fn item(&self) -> &Item<K, V> {
+        self.node.keys[self.slot].as_deref().unwrap()
+    }
+
+    // puts a new entry into the gap the handle points at, and returns its value
+    pub(crate) fn insert(self, key: K, value: V) -> &'a mut V {
+        let Handle {
+            node,
+            slot,
+            counters,
+            size,
+            ..
+        } = self;
+        node.insert_to_keys(slot, key, value);
+        for counter in counters {
+            *counter += 1;
+        }
+        *size += 1;
+        node.keys[slot].as_mut().unwrap().value.as_mut()
+    }
+}
@@ -1,12 +1,19 @@
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering::{Equal, Greater, Less},
+};
 
 use crate::{stc::Node, KEY_ARRAY};
 
 impl<K: Ord, V> Node<K, V> {
-    pub fn get(&self, key: &K) -> Option<(&K, &V)> {
+    pub fn get<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         'search: for index in 0..KEY_ARRAY {
             match &self.keys[index] {
-                Some(item) => match key.cmp(&item.key) {
+                Some(item) => match key.cmp((*item.key).borrow()) {
                     Less => {
                         if let Some(pointer) = &self.pointers[index] {
                             return pointer.child.get(key);
@@ -39,10 +46,14 @@ impl<K: Ord, V> Node<K, V> {
         None
     }
 
-    pub fn get_index_from_key(&self, key: &K, mut usize: usize) -> Option<usize> {
+    pub fn get_index_from_key<Q>(&self, key: &Q, mut usize: usize) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         'search: for index in 0..KEY_ARRAY {
             match &self.keys[index] {
-                Some(item) => match key.cmp(&item.key) {
+                Some(item) => match key.cmp((*item.key).borrow()) {
                     Less => {
                         if let Some(pointer) = &self.pointers[index] {
                             return pointer.child.get_index_from_key(key, usize);
@@ -88,10 +99,14 @@ impl<K: Ord, V> Node<K, V> {
         None
     }
 
-    pub fn get_mut(&mut self, key: &K) -> Option<(&mut K, &mut V)> {
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<(&mut K, &mut V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         'search: for (index, item) in self.keys.iter_mut().enumerate() {
             match item {
-                Some(item) => match key.cmp(&item.key) {
+                Some(item) => match key.cmp((*item.key).borrow()) {
                     Less => {
                         if let Some(pointer) = self.pointers[index].as_mut() {
                             return pointer.child.get_mut(key);
@@ -0,0 +1,106 @@
+use std::{
+    borrow::Borrow,
+    ops::{Index, IndexMut},
+};
+
+use crate::{IndexTreeMap, IndexTreeSet};
+
+/// A position in an [`IndexTreeMap`] or [`IndexTreeSet`], used to index by rank rather than by key.
+///
+/// # Example
+///
+/// Basic usage:
+/// ```rust
+/// use indextreemap::{IndexTreeMap, Pos};
+///
+/// let mut map = IndexTreeMap::new();
+/// map.insert(10, "a");
+/// map.insert(20, "b");
+///
+/// assert_eq!(map[Pos(1)], "b");
+/// assert_eq!(map[&10], "a");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos(pub usize);
+
+impl<K, Q, V> Index<&Q> for IndexTreeMap<K, V>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in IndexTreeMap")
+    }
+}
+
+impl<K, V> Index<Pos> for IndexTreeMap<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value at the supplied position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    fn index(&self, pos: Pos) -> &V {
+        match self.size > pos.0 {
+            true => self.root.get_from_index(pos.0).unwrap().1,
+            false => out_of_bounds(pos, self.size),
+        }
+    }
+}
+
+impl<K, V> IndexMut<Pos> for IndexTreeMap<K, V> {
+    /// Returns a mutable reference to the value at the supplied position.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::{IndexTreeMap, Pos};
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(10, 1);
+    /// map[Pos(0)] += 1;
+    /// assert_eq!(map.get(&10), Some(&2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    fn index_mut(&mut self, pos: Pos) -> &mut V {
+        match self.size > pos.0 {
+            true => self.root.get_mut_from_index(pos.0).unwrap().1,
+            false => out_of_bounds(pos, self.size),
+        }
+    }
+}
+
+impl<K> Index<Pos> for IndexTreeSet<K> {
+    type Output = K;
+
+    /// Returns a reference to the key at the supplied position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is out of bounds.
+    fn index(&self, pos: Pos) -> &K {
+        match self.map.size > pos.0 {
+            true => self.map.root.get_from_index(pos.0).unwrap().0,
+            false => out_of_bounds(pos, self.map.size),
+        }
+    }
+}
+
+fn out_of_bounds(pos: Pos, len: usize) -> ! {
+    panic!(
+        "position out of bounds in IndexTreeMap: the len is {len} but the position is {}",
+        pos.0
+    )
+}
@@ -6,10 +6,18 @@ use crate::{
     },
     KEY_ARRAY,
 };
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::{
+    cmp::Ordering::{Equal, Greater, Less},
+    mem,
+};
 
-impl<K: Ord + Clone, V: Clone> Node<K, V> {
+impl<K: Ord, V> Node<K, V> {
     pub fn insert(&mut self, key: K, value: V) -> Output<K, V> {
+        self.insert_full(key, value, &mut 0)
+    }
+
+    // inserts like `insert`, adding the number of items that precede the key to `rank` on the way down
+    pub fn insert_full(&mut self, key: K, value: V, rank: &mut usize) -> Output<K, V> {
         // if node is a leaf then the node has no pointers
 
         match self.leaf {
@@ -34,16 +42,18 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
                     if let Some(k) = self.keys[index].as_mut() {
                         match &key.cmp(&k.key) {
                             Less => {
+                                *rank += index;
                                 self.insert_to_keys(index, key, value);
                                 break;
                             }
                             Equal => {
-                                k.value = Box::new(value);
-                                return KeyExists;
+                                *rank += index;
+                                return KeyExists(mem::replace(&mut *k.value, value));
                             }
                             Greater => continue 'search,
                         }
                     } else {
+                        *rank += index;
                         self.keys[index] = Some(Box::new(Item::new(key, value)));
                         self.n += 1;
                         break;
@@ -61,8 +71,9 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
                     if let Some(k) = self.keys[index].as_mut() {
                         match key.cmp(&k.key) {
                             Less => {
+                                *rank += self.rank_before(index);
                                 let output = if let Some(pointer) = self.pointers[index].as_mut() {
-                                    pointer.child.insert(key, value)
+                                    pointer.child.insert_full(key, value, rank)
                                 } else {
                                     let mut pointer = Pointer::new();
                                     pointer.child.insert(key, value);
@@ -70,7 +81,7 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
                                     KeyIsNew
                                 };
                                 match output {
-                                    KeyExists => return output,
+                                    KeyExists(_) => return output,
                                     NewKeyPointer(new_key, new_pointer) => {
                                         self.pointers[index].as_mut().unwrap().counter =
                                             self.pointers[index].as_ref().unwrap().child.size();
@@ -87,16 +98,18 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
                                 }
                             }
                             Equal => {
-                                k.value = Box::new(value);
-                                return KeyExists;
+                                *rank += self.rank_before(index + 1) - 1;
+                                let k = self.keys[index].as_mut().unwrap();
+                                return KeyExists(mem::replace(&mut *k.value, value));
                             }
                             Greater => {
                                 continue 'search;
                             }
                         }
                     } else {
+                        *rank += self.rank_before(index);
                         let output = if let Some(pointer) = self.pointers[index].as_mut() {
-                            pointer.child.insert(key, value)
+                            pointer.child.insert_full(key, value, rank)
                         } else {
                             let mut pointer = Pointer::new();
                             pointer.child.insert(key, value);
@@ -104,7 +117,7 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
                             KeyIsNew
                         };
                         match output {
-                            KeyExists => return output,
+                            KeyExists(_) => return output,
                             NewKeyPointer(new_key, new_pointer) => {
                                 self.pointers[index].as_mut().unwrap().counter =
                                     self.pointers[index].as_ref().unwrap().child.size();
@@ -122,6 +135,17 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
         }
         Null
     }
+}
+
+impl<K, V> Node<K, V> {
+    // the number of items in this subtree that come before the pointer at `index`
+    pub fn rank_before(&self, index: usize) -> usize {
+        let children: usize = self.pointers[..index]
+            .iter()
+            .map(|pointer| pointer.as_ref().map_or(0, |p| p.counter))
+            .sum();
+        index + children
+    }
 
     pub fn insert_key_pointer(
         &mut self,
@@ -147,6 +171,41 @@ impl<K: Ord + Clone, V: Clone> Node<K, V> {
         }
     }
 
+    // splits the child at `index` around its middle key, which moves up into this node;
+    // this node must have room for one more key
+    pub fn split_child(&mut self, index: usize) {
+        let Some(pointer) = self.pointers[index].as_mut() else {
+            return;
+        };
+        let child = &mut pointer.child;
+        let middle = child.n / 2;
+
+        let mut right = Node::new();
+        right.leaf = child.leaf;
+        right.n = child.n - middle - 1;
+        for loc in 0..right.n {
+            right.keys[loc] = child.keys[middle + 1 + loc].take();
+        }
+        if !child.leaf {
+            for loc in 0..=right.n {
+                right.pointers[loc] = child.pointers[middle + 1 + loc].take();
+            }
+        }
+        let key = child.keys[middle].take();
+        child.n = middle;
+        pointer.counter = child.size();
+        let counter = right.size();
+
+        self.keys[index..=self.n].rotate_right(1);
+        self.keys[index] = key;
+        self.pointers[index + 1..=self.n + 1].rotate_right(1);
+        self.pointers[index + 1] = Some(Pointer {
+            child: right,
+            counter,
+        });
+        self.n += 1;
+    }
+
     pub fn insert_to_keys(&mut self, index: usize, key: K, value: V) {
         let (_, right) = self.keys.split_at_mut(index);
         right.rotate_right(1);
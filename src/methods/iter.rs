@@ -1,134 +1,196 @@
-use crate::{IndexTreeMap, IndexTreeSet};
+use crate::{
+    methods::{
+        range::{IndexTreeRange, IndexTreeRangeMut, IndexTreeSetRange},
+        traverse::{adapter, Walk},
+    },
+    stc::Node,
+    IndexTreeMap, IndexTreeSet,
+};
 
 //Iterator
-pub struct IndexTreeIterator<'a, K, V> {
-    pub tree: &'a IndexTreeMap<K, V>,
-    pub index: usize,
+pub type IndexTreeIterator<'a, K, V> = IndexTreeRange<'a, K, V>;
+
+//Set Iterator
+pub type IndexTreeSetIterator<'a, K> = IndexTreeSetRange<'a, K>;
+
+//IntoIterator
+pub type IndexTreeIntoIterator<K, V> = Walk<Box<Node<K, V>>>;
+
+impl<K, V> IntoIterator for IndexTreeMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IndexTreeIntoIterator<K, V>;
+
+    /// Creates a consuming iterator visiting all the key-value pairs, in sorted order. The map cannot be used after calling this.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let items: Vec<(i32, &str)> = map.into_iter().collect();
+    /// assert_eq!(items, [(1, "a"), (2, "b")]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        IndexTreeIntoIterator::new(self.root, self.size, 0, self.size)
+    }
+}
+
+//IntoKeys
+adapter!(IndexTreeIntoKeys<K, V>, iter: IndexTreeIntoIterator<K, V>, K, |(key, _)| key);
+
+//IntoValues
+adapter!(IndexTreeIntoValues<K, V>, iter: IndexTreeIntoIterator<K, V>, V, |(_, value)| value);
+
+//Set IntoIterator
+adapter!(IndexTreeSetIntoIterator<K>, iter: IndexTreeIntoIterator<K, ()>, K, |(key, _)| key);
+
+impl<K> IntoIterator for IndexTreeSet<K> {
+    type Item = K;
+    type IntoIter = IndexTreeSetIntoIterator<K>;
+
+    /// Creates a consuming iterator visiting all the keys, in sorted order. The set cannot be used after calling this.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// set.insert(2);
+    /// set.insert(1);
+    ///
+    /// let keys: Vec<i32> = set.into_iter().collect();
+    /// assert_eq!(keys, [1, 2]);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        IndexTreeSetIntoIterator {
+            iter: self.map.into_iter(),
+        }
+    }
 }
 
-impl<'a, K: Ord + Clone, V: Clone> Iterator for IndexTreeIterator<'a, K, V> {
+//Borrowed IntoIterator
+impl<'a, K, V> IntoIterator for &'a IndexTreeMap<K, V> {
     type Item = (&'a K, &'a V);
+    type IntoIter = IndexTreeIterator<'a, K, V>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.tree.size {
-            self.index += 1;
-            return self.tree.get_key_value_from_index(self.index - 1);
-        }
-        None
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-//Iterator
-pub struct IndexTreeSetIterator<'a, K> {
-    pub tree: &'a IndexTreeSet<K>,
-    pub index: usize,
+impl<'a, K, V> IntoIterator for &'a mut IndexTreeMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IndexTreeRangeMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
 }
 
-impl<'a, K: Ord + Clone> Iterator for IndexTreeSetIterator<'a, K> {
+impl<'a, K> IntoIterator for &'a IndexTreeSet<K> {
     type Item = &'a K;
+    type IntoIter = IndexTreeSetIterator<'a, K>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.tree.map.size {
-            self.index += 1;
-            return self.tree.get_key_from_index(self.index - 1);
-        }
-        None
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-// // IntoIterator
-// pub struct IndexTreeIntoIterator<K, V> {
-//     pub tree: IndexTreeMap<K, V>,
-// }
-
-// impl<K: Ord + Clone, V: Clone> IntoIterator for IndexTreeMap<K, V> {
-//     type Item = (K, V);
-//     type IntoIter = IndexTreeIntoIterator<K, V>;
-
-//     /// Creates a consuming iterator visiting all the keys, in sorted PartialOrder. The map cannot be used after calling this.
-//     ///
-//     /// # Example
-//     ///
-//     /// Basic usage:
-//     /// ```rust
-//     /// use std::collections::BTreeMap;
-//     ///
-//     /// let mut map = BTreeMap::new();
-//     /// map.insert(2, "b");
-//     /// map.insert(1, "a");
-//     ///
-//     /// let items: Vec<(i32, &str)> = map.into_iter().collect();
-//     /// assert_eq!(items, [(1, "a"), (2, "b")]);
-//     /// ```
-//     fn into_iter(self) -> Self::IntoIter {
-//         IndexTreeIntoIterator { tree: self }
-//     }
-// }
-
-// impl<K: Ord + Clone, V: Clone> Iterator for IndexTreeIntoIterator<K, V> {
-//     type Item = (K, V);
-
-//     fn next(&mut self) -> Option<Self::Item> {
-//         if self.tree.size == 0 {
-//             return None;
-//         }
-//         self.tree.remove_from_index(0)
-//     }
-// }
-
 // FromIter
-impl<K: Ord + Clone, V: Clone> IndexTreeMap<K, V> {
-    fn add(&mut self, item: (K, V)) {
-        self.insert(item.0, item.1);
+impl<K: Ord, V> FromIterator<(K, V)> for IndexTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        IndexTreeMap::from_unsorted_vec(iter.into_iter().collect())
     }
 }
 
-impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)>
-    for IndexTreeMap<K, V>
-{
-    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
-        let mut c = IndexTreeMap::new();
+impl<K: Ord, V, const N: usize> From<[(K, V); N]> for IndexTreeMap<K, V> {
+    /// Converts a `[(K, V); N]` into an `IndexTreeMap<K, V>`.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let map1 = IndexTreeMap::from([(1, 2), (3, 4)]);
+    /// let map2: IndexTreeMap<_, _> = [(3, 4), (1, 2)].into();
+    /// assert_eq!(map1, map2);
+    /// ```
+    fn from(arr: [(K, V); N]) -> Self {
+        IndexTreeMap::from_unsorted_vec(Vec::from(arr))
+    }
+}
 
-        for i in iter {
-            c.add(i);
+impl<K: Ord> FromIterator<K> for IndexTreeSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        IndexTreeSet {
+            map: iter.into_iter().map(|key| (key, ())).collect(),
         }
-
-        c
     }
 }
 
-//Keys
-pub struct IndexTreeKeys<'a, K, V> {
-    pub tree: &'a IndexTreeMap<K, V>,
-    pub index: usize,
+impl<K: Ord, const N: usize> From<[K; N]> for IndexTreeSet<K> {
+    /// Converts a `[K; N]` into an `IndexTreeSet<K>`.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let set1 = IndexTreeSet::from([1, 2, 3, 4]);
+    /// let set2: IndexTreeSet<_> = [4, 3, 2, 1].into();
+    /// assert_eq!(set1, set2);
+    /// ```
+    fn from(arr: [K; N]) -> Self {
+        arr.into_iter().collect()
+    }
 }
 
-impl<'a, K: Ord + Clone, V: Clone> Iterator for IndexTreeKeys<'a, K, V> {
-    type Item = &'a K;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.tree.size {
-            self.index += 1;
-            return self.tree.get_key_from_index(self.index - 1);
+//Extend
+// an empty map is bulk loaded, otherwise each item is inserted in turn
+impl<K: Ord, V> Extend<(K, V)> for IndexTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        if self.is_empty() {
+            *self = iter.into_iter().collect();
+            return;
+        }
+        for (key, value) in iter {
+            self.insert(key, value);
         }
-        None
     }
 }
 
-//Values
-pub struct IndexTreeValues<'a, K, V> {
-    pub tree: &'a IndexTreeMap<K, V>,
-    pub index: usize,
+impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for IndexTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(key, value)| (*key, *value)));
+    }
 }
 
-impl<'a, K: Ord + Clone, V: Clone> Iterator for IndexTreeValues<'a, K, V> {
-    type Item = &'a V;
+impl<K: Ord> Extend<K> for IndexTreeSet<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|key| (key, ())));
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.tree.size {
-            self.index += 1;
-            return self.tree.get_from_index(self.index - 1);
-        }
-        None
+impl<'a, K: Ord + Copy> Extend<&'a K> for IndexTreeSet<K> {
+    fn extend<I: IntoIterator<Item = &'a K>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
     }
 }
+
+//Keys
+adapter!(IndexTreeKeys<'a, K, V>, iter: IndexTreeRange<'a, K, V>, &'a K, |(key, _)| key);
+
+//Values
+adapter!(IndexTreeValues<'a, K, V>, iter: IndexTreeRange<'a, K, V>, &'a V, |(_, value)| value);
+
+//Values Mut
+adapter!(IndexTreeValuesMut<'a, K, V>, iter: IndexTreeRangeMut<'a, K, V>, &'a mut V, |(_, value)| value);
@@ -0,0 +1,208 @@
+use std::mem;
+
+use crate::{
+    stc::{
+        Item, Node,
+        Output::{self, NewKeyPointer, Null},
+        Pointer,
+    },
+    IndexTreeMap,
+};
+
+impl<K, V> Node<K, V> {
+    // the number of levels below this node; all leaves sit at the same depth
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut node = self;
+        while let (false, Some(pointer)) = (node.leaf, &node.pointers[0]) {
+            node = &pointer.child;
+            height += 1;
+        }
+        height
+    }
+
+    // appends `item` and then the subtree `right` to the right edge, `depth` levels below this node.
+    // a node that overflows splits and hands its upper half to its parent
+    pub fn push_back(
+        &mut self,
+        item: Box<Item<K, V>>,
+        right: Option<Pointer<K, V>>,
+        depth: usize,
+    ) -> Output<K, V> {
+        if depth > 0 {
+            let last = self.n;
+            let Some(pointer) = self.pointers[last].as_mut() else {
+                return Null;
+            };
+            pointer.counter += 1 + right.as_ref().map_or(0, |p| p.counter);
+            return match pointer.child.push_back(item, right, depth - 1) {
+                NewKeyPointer(key, new_pointer) => {
+                    pointer.counter -= 1 + new_pointer.as_ref().map_or(0, |p| p.counter);
+                    self.insert_key_pointer(last, key, new_pointer)
+                }
+                output => output,
+            };
+        }
+
+        let has_child = right.is_some();
+        self.keys[self.n] = Some(item);
+        self.pointers[self.n + 1] = right;
+        self.n += 1;
+        if has_child {
+            self.balance_children(self.n - 1);
+        }
+        self.split_if_full()
+    }
+
+    // prepends the subtree `left` and then `item` to the left edge, `depth` levels below this node
+    pub fn push_front(
+        &mut self,
+        item: Box<Item<K, V>>,
+        left: Option<Pointer<K, V>>,
+        depth: usize,
+    ) -> Output<K, V> {
+        if depth > 0 {
+            let Some(pointer) = self.pointers[0].as_mut() else {
+                return Null;
+            };
+            pointer.counter += 1 + left.as_ref().map_or(0, |p| p.counter);
+            return match pointer.child.push_front(item, left, depth - 1) {
+                NewKeyPointer(key, new_pointer) => {
+                    pointer.counter -= 1 + new_pointer.as_ref().map_or(0, |p| p.counter);
+                    self.insert_key_pointer(0, key, new_pointer)
+                }
+                output => output,
+            };
+        }
+
+        let has_child = left.is_some();
+        self.keys[..=self.n].rotate_right(1);
+        self.keys[0] = Some(item);
+        if has_child {
+            self.pointers[..=self.n + 1].rotate_right(1);
+            self.pointers[0] = left;
+        }
+        self.n += 1;
+        if has_child {
+            self.balance_children(0);
+        }
+        self.split_if_full()
+    }
+
+    fn split_if_full(&mut self) -> Output<K, V> {
+        match (self.is_full(), self.leaf) {
+            (false, _) => Null,
+            (true, true) => self.split_leaf(),
+            (true, false) => self.split_parent(),
+        }
+    }
+}
+
+impl<K, V> IndexTreeMap<K, V> {
+    // appends every entry of `right`, whose keys must all be greater than the keys in this map,
+    // grafting the smaller tree onto the edge of the taller one
+    pub(crate) fn join(&mut self, mut right: IndexTreeMap<K, V>) {
+        if right.size == 0 {
+            return;
+        }
+        if self.size == 0 {
+            *self = right;
+            return;
+        }
+        let size = self.size + right.size;
+
+        let output = if self.root.height() >= right.root.height() {
+            let Some(item) = right.root.take_first_key() else {
+                return;
+            };
+            right.root.collapse_root();
+            right.size -= 1;
+            let (left_height, right_height) = (self.root.height(), right.root.height());
+
+            if right.size == 0 {
+                self.root.push_back(item, None, left_height)
+            } else if left_height == right_height {
+                let left = Pointer {
+                    child: mem::replace(&mut self.root, Node::new()),
+                    counter: self.size,
+                };
+                let right = Pointer {
+                    child: right.root,
+                    counter: right.size,
+                };
+                self.root = Node::join_roots(left, item, right);
+                Null
+            } else {
+                let pointer = Pointer {
+                    child: right.root,
+                    counter: right.size,
+                };
+                self.root
+                    .push_back(item, Some(pointer), left_height - right_height - 1)
+            }
+        } else {
+            let Some(item) = self.root.take_last_key() else {
+                return;
+            };
+            self.root.collapse_root();
+            self.size -= 1;
+            let (left_height, right_height) = (self.root.height(), right.root.height());
+
+            if self.size == 0 {
+                let output = right.root.push_front(item, None, right_height);
+                self.root = right.root;
+                output
+            } else if left_height == right_height {
+                let left = Pointer {
+                    child: mem::replace(&mut self.root, Node::new()),
+                    counter: self.size,
+                };
+                let right = Pointer {
+                    child: right.root,
+                    counter: right.size,
+                };
+                self.root = Node::join_roots(left, item, right);
+                Null
+            } else {
+                let pointer = Pointer {
+                    child: mem::replace(&mut self.root, right.root),
+                    counter: self.size,
+                };
+                self.root
+                    .push_front(item, Some(pointer), right_height - left_height - 1)
+            }
+        };
+
+        if let NewKeyPointer(key, pointer) = output {
+            self.root.update_root(key, pointer);
+        }
+        self.root.collapse_root();
+        self.size = size;
+    }
+
+    // detaches the entries at indexes [start, end) as a map of their own
+    pub(crate) fn take_range(&mut self, start: usize, end: usize) -> IndexTreeMap<K, V> {
+        let tail = self.split_off_from_index(end);
+        let range = self.split_off_from_index(start);
+        self.join(tail);
+        range
+    }
+}
+
+impl<K, V> Node<K, V> {
+    // puts two trees of the same height under a new root, separated by `item`
+    fn join_roots(
+        left: Pointer<K, V>,
+        item: Box<Item<K, V>>,
+        right: Pointer<K, V>,
+    ) -> Box<Node<K, V>> {
+        let mut root = Node::new();
+        root.keys[0] = Some(item);
+        root.pointers[0] = Some(left);
+        root.pointers[1] = Some(right);
+        root.n = 1;
+        root.leaf = false;
+        root.balance_children(0);
+        root
+    }
+}
@@ -1,8 +1,18 @@
+pub mod build;
+pub mod compare;
+pub mod cursor;
+pub mod entry;
 pub mod get;
+pub mod index;
 pub mod insert;
 pub mod iter;
+pub mod join;
+pub mod range;
 pub mod remove;
 pub mod replace;
+pub mod retain;
 pub mod search;
+pub mod slice;
 pub mod split;
 pub mod take;
+pub mod traverse;
@@ -0,0 +1,76 @@
+use std::{
+    borrow::Borrow,
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeBounds,
+    },
+};
+
+use crate::{
+    methods::traverse::{adapter, Walk},
+    stc::Node,
+    IndexTreeMap,
+};
+
+//Range
+pub type IndexTreeRange<'a, K, V> = Walk<&'a Node<K, V>>;
+
+//Range Mut
+pub type IndexTreeRangeMut<'a, K, V> = Walk<&'a mut Node<K, V>>;
+
+//Set Range
+adapter!(IndexTreeSetRange<'a, K>, range: IndexTreeRange<'a, K, ()>, &'a K, |(key, _)| key);
+
+// converts a range of indexes to [start, end), clamped to the length of the tree
+pub(crate) fn index_range_from_bounds<R: RangeBounds<usize>>(
+    range: &R,
+    len: usize,
+) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Included(index) => *index,
+        Excluded(index) => index.saturating_add(1),
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(index) => index.saturating_add(1),
+        Excluded(index) => *index,
+        Unbounded => usize::MAX,
+    };
+    if start > end {
+        panic!("range start is greater than range end in IndexTreeMap")
+    }
+    let end = end.min(len);
+    (start.min(end), end)
+}
+
+impl<K: Ord, V> IndexTreeMap<K, V> {
+    // converts a range of keys to the range of indexes [start, end) they cover
+    pub(crate) fn index_range_from_key_range<Q, R>(&self, range: &R) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        match (range.start_bound(), range.end_bound()) {
+            (Excluded(start), Excluded(end)) if start == end => {
+                panic!("range start and end are equal and excluded in IndexTreeMap")
+            }
+            (Included(start) | Excluded(start), Included(end) | Excluded(end)) if start > end => {
+                panic!("range start is greater than range end in IndexTreeMap")
+            }
+            _ => {}
+        }
+
+        let start = match range.start_bound() {
+            Included(key) => self.lower_bound_index(key),
+            Excluded(key) => self.upper_bound_index(key),
+            Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Included(key) => self.upper_bound_index(key),
+            Excluded(key) => self.lower_bound_index(key),
+            Unbounded => self.size,
+        };
+        (start, end)
+    }
+}
@@ -1,196 +1,259 @@
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering::{Equal, Greater, Less},
+};
 
-use crate::{stc::Node, KEY_ARRAY, POINTER_ARRAY};
+use crate::{
+    stc::{Item, Node, Pointer},
+    MIN_KEYS,
+};
+
+impl<K: Ord, V> Node<K, V> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_item(key).map(|item| (*item.key, *item.value))
+    }
+
+    pub fn remove_item<Q>(&mut self, key: &Q) -> Option<Box<Item<K, V>>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut index = 0;
+        'search: while index < self.n {
+            match &self.keys[index] {
+                Some(item) => match key.cmp((*item.key).borrow()) {
+                    Less => break 'search,
+                    Equal => return self.remove_key(index),
+                    Greater => index += 1,
+                },
+                None => break 'search,
+            }
+        }
 
-impl<K: Ord + Clone, V: Clone> Node<K, V> {
-    pub fn remove(&mut self, key: &K) -> Option<(K, V)> {
         if self.leaf {
-            'search: for index in 0..KEY_ARRAY {
-                if let Some(item) = self.keys[index].as_mut() {
-                    match key.cmp(&item.key) {
-                        Less => return None,
-                        Equal => return self.leaf_remove_key(index),
-                        Greater => continue 'search,
-                    }
+            return None;
+        }
+        let pointer = self.pointers[index].as_mut()?;
+        let output = pointer.child.remove_item(key);
+        if output.is_some() {
+            pointer.counter -= 1;
+            self.rebalance_child(index);
+        }
+        output
+    }
+}
+
+impl<K, V> Node<K, V> {
+    pub fn remove_index(&mut self, index: usize) -> Option<(K, V)> {
+        self.remove_item_at(index)
+            .map(|item| (*item.key, *item.value))
+    }
+
+    pub fn remove_item_at(&mut self, mut index: usize) -> Option<Box<Item<K, V>>> {
+        if self.leaf {
+            if index < self.n {
+                return self.remove_key(index);
+            }
+            return None;
+        }
+
+        for loc in 0..=self.n {
+            let counter = self.pointers[loc].as_ref().map_or(0, |p| p.counter);
+            if index < counter {
+                let pointer = self.pointers[loc].as_mut()?;
+                let output = pointer.child.remove_item_at(index);
+                if output.is_some() {
+                    pointer.counter -= 1;
+                    self.rebalance_child(loc);
                 }
+                return output;
             }
-            None
-        } else {
-            'search: for index in 0..KEY_ARRAY {
-                match self.keys[index].as_mut() {
-                    Some(item) => match key.cmp(&item.key) {
-                        Less => {
-                            if let Some(pointer) = self.pointers[index].as_mut() {
-                                let output = pointer.child.remove(key);
-                                if output.is_some() {
-                                    pointer.counter -= 1;
-                                }
-                                return output;
-                            } else {
-                                return None;
-                            }
-                        }
-                        Equal => return self.node_remove_key(index),
-                        Greater => {
-                            if index >= KEY_ARRAY - 1 {
-                                if let Some(pointer) = self.pointers[index + 1].as_mut() {
-                                    let output = pointer.child.remove(key);
-                                    if output.is_some() {
-                                        pointer.counter -= 1;
-                                    }
-                                    return output;
-                                } else {
-                                    return None;
-                                }
-                            } else {
-                                continue 'search;
-                            }
-                        }
-                    },
-                    None => {
-                        if let Some(pointer) = self.pointers[index].as_mut() {
-                            let output = pointer.child.remove(key);
-                            if output.is_some() {
-                                pointer.counter -= 1;
-                            }
-                            return output;
-                        } else {
-                            return None;
-                        }
-                    }
+            index -= counter;
+
+            if loc < self.n {
+                if index == 0 {
+                    return self.remove_key(loc);
                 }
+                index -= 1;
             }
-            None
         }
+        None
     }
 
-    pub fn remove_index(&mut self, mut index: usize) -> Option<(K, V)> {
-        if self.leaf {
-            self.leaf_remove_key(index)
-        } else {
-            for loc in 0..KEY_ARRAY {
-                if let Some(pointer) = self.pointers[loc].as_mut() {
-                    if index < pointer.counter {
-                        return pointer.child.remove_index(index);
-                    } else {
-                        index -= pointer.counter
-                    };
-                    if index == 0 {
-                        return self.node_remove_key(index);
-                    } else {
-                        index -= 1;
-                        continue;
+    // removes the key at `index`; on an internal node its place is taken by the in-order predecessor
+    pub fn remove_key(&mut self, index: usize) -> Option<Box<Item<K, V>>> {
+        if !self.leaf {
+            let predecessor = match self.pointers[index].as_mut() {
+                Some(pointer) => {
+                    let item = pointer.child.take_last_key();
+                    if item.is_some() {
+                        pointer.counter -= 1;
                     }
-                } else {
-                    continue;
+                    item
                 }
+                None => None,
+            };
+            if let Some(item) = predecessor {
+                let output = self.keys[index].replace(item);
+                self.rebalance_child(index);
+                return output;
             }
-            None
         }
-    }
-}
 
-impl<K: Ord, V> Node<K, V> {
-    pub fn node_remove_key(&mut self, index: usize) -> Option<(K, V)> {
-        // if both pointer children pointers[index] + pointers[index+1] are less than KEY_ARRAY, then we can merge the right pointer to the left pointer
-        let output = self.keys[index].take().map(|item| (*item.key, *item.value));
+        // a leaf, or an internal key without a left subtree: close the gap it leaves behind
+        let output = self.keys[index].take();
         if output.is_some() {
-            self.n -= 1;
-        }
-        if let [Some(left_pointer), Some(right_pointer)] = self.pointers[index..index + 2].as_mut()
-        {
-            if left_pointer.child.n + right_pointer.child.n < KEY_ARRAY {
-                self.merge_pointers_from_removed_node(index);
-            } else {
-                self.replace_removed_key(index);
+            self.keys[index..self.n].rotate_left(1);
+            if !self.leaf {
+                self.pointers[index..=self.n].rotate_left(1);
+                self.pointers[self.n] = None;
             }
-        } else {
-            self.replace_removed_key(index);
+            self.n -= 1;
         }
         output
     }
 
-    pub fn merge_pointers_from_removed_node(&mut self, index: usize) {
-        if let [Some(left_pointer), Some(right_pointer)] = self.pointers[index..index + 2].as_mut()
-        {
-            left_pointer.counter += right_pointer.counter;
-            left_pointer.child.n += right_pointer.child.n;
-            let mut count = 0;
-            for loc in 0..KEY_ARRAY {
-                match &left_pointer.child.keys[loc] {
-                    Some(_) => continue,
-                    None => {
-                        left_pointer.child.keys[loc] = right_pointer.child.keys[count].take();
-                        count += 1;
-                        continue;
-                    }
-                }
-            }
-            count = 0;
-            for loc in 0..POINTER_ARRAY {
-                match &left_pointer.child.pointers[loc] {
-                    Some(_) => continue,
-                    None => {
-                        left_pointer.child.pointers[loc] =
-                            right_pointer.child.pointers[count].take();
-                        count += 1;
-                        continue;
-                    }
-                }
+    // tops up the child at `index` after a removal left it with fewer than MIN_KEYS keys,
+    // either by rotating a key through the parent from a sibling or by merging with one
+    pub fn rebalance_child(&mut self, index: usize) {
+        if let Some(pointer) = &self.pointers[index] {
+            if pointer.child.n < MIN_KEYS {
+                self.top_up_child(index);
             }
+        }
+    }
 
-            self.pointers[index + 1] = None;
-            for i in 0..(KEY_ARRAY - 1) {
-                if i >= index {
-                    self.keys[i] = self.keys[i + 1].take();
-                    self.pointers[i + 1] = self.pointers[i + 2].take();
-                }
-            }
+    // gives the child at `index` one more key, rotated in from a sibling that can spare one,
+    // or merges it with a sibling when neither can
+    pub fn top_up_child(&mut self, index: usize) {
+        let leaf = match &self.pointers[index] {
+            Some(pointer) => pointer.child.leaf,
+            None => return,
+        };
+
+        let sibling = |pointer: &Option<Pointer<K, V>>| match pointer {
+            Some(pointer) if pointer.child.leaf == leaf => Some(pointer.child.n),
+            _ => None,
+        };
+        let left = match index {
+            0 => None,
+            _ => sibling(&self.pointers[index - 1]),
+        };
+        let right = match index < self.n {
+            true => sibling(&self.pointers[index + 1]),
+            false => None,
+        };
+
+        match (left, right) {
+            (Some(n), _) if n > MIN_KEYS => self.rotate_right(index - 1),
+            (_, Some(n)) if n > MIN_KEYS => self.rotate_left(index),
+            (Some(_), _) => self.merge_children(index - 1),
+            (_, Some(_)) => self.merge_children(index),
+            _ => {}
         }
+    }
 
-        if self.is_empty() {
-            if let Some(pointer) = self.pointers[index].as_mut() {
-                self.keys = pointer.child.take_keys();
-                self.n = pointer.child.n;
-                self.leaf = pointer.child.leaf;
-                self.pointers = pointer.child.take_pointers();
-            }
+    // moves the last key of the child at `index` up into the parent, and the parent key down into the next child
+    pub fn rotate_right(&mut self, index: usize) {
+        let (left, right) = self.pointers.split_at_mut(index + 1);
+        let (Some(left), Some(right)) = (left[index].as_mut(), right[0].as_mut()) else {
+            return;
+        };
+
+        let mut moved = 1;
+        let left_node = &mut left.child;
+        let right_node = &mut right.child;
+
+        left_node.n -= 1;
+        let item = left_node.keys[left_node.n].take();
+        let separator = std::mem::replace(&mut self.keys[index], item);
+        right_node.keys[..=right_node.n].rotate_right(1);
+        right_node.keys[0] = separator;
+
+        if !right_node.leaf {
+            let pointer = left_node.pointers[left_node.n + 1].take();
+            moved += pointer.as_ref().map_or(0, |p| p.counter);
+            right_node.pointers[..=right_node.n + 1].rotate_right(1);
+            right_node.pointers[0] = pointer;
         }
+        right_node.n += 1;
+
+        left.counter -= moved;
+        right.counter += moved;
     }
 
-    pub fn replace_removed_key(&mut self, index: usize) {
-        match self.pointers[index].as_mut() {
-            Some(p) => {
-                self.keys[index] = {
-                    let output = p.child.take_last_key();
-                    if output.is_some() {
-                        p.counter -= 1;
-                    }
-                    output
-                }
-            }
-            None => {
-                for i in 0..(KEY_ARRAY - 1) {
-                    if i >= index {
-                        self.keys[i] = self.keys[i + 1].take();
-                        self.pointers[i] = self.pointers[i + 1].take()
-                    }
-                }
-                self.pointers[KEY_ARRAY - 1] = self.pointers[KEY_ARRAY].take()
-            }
+    // moves the first key of the child at `index + 1` up into the parent, and the parent key down into the previous child
+    pub fn rotate_left(&mut self, index: usize) {
+        let (left, right) = self.pointers.split_at_mut(index + 1);
+        let (Some(left), Some(right)) = (left[index].as_mut(), right[0].as_mut()) else {
+            return;
         };
+
+        let mut moved = 1;
+        let left_node = &mut left.child;
+        let right_node = &mut right.child;
+
+        let item = right_node.keys[0].take();
+        right_node.keys[..right_node.n].rotate_left(1);
+        let separator = std::mem::replace(&mut self.keys[index], item);
+        left_node.keys[left_node.n] = separator;
+
+        if !left_node.leaf {
+            let pointer = right_node.pointers[0].take();
+            moved += pointer.as_ref().map_or(0, |p| p.counter);
+            right_node.pointers[..=right_node.n].rotate_left(1);
+            left_node.pointers[left_node.n + 1] = pointer;
+        }
+        right_node.n -= 1;
+        left_node.n += 1;
+
+        left.counter += moved;
+        right.counter -= moved;
     }
 
-    pub fn leaf_remove_key(&mut self, index: usize) -> Option<(K, V)> {
-        let output = self.keys[index].take().map(|item| (*item.key, *item.value));
-        if output.is_some() {
-            self.n -= 1;
+    // folds the child at `index + 1` and the key between them into the child at `index`
+    pub fn merge_children(&mut self, index: usize) {
+        if self.pointers[index].is_none() {
+            return;
         }
-        for i in 0..(KEY_ARRAY - 1) {
-            if i >= index {
-                self.keys[i] = self.keys[i + 1].take();
+        let Some(Pointer {
+            child: mut right_node,
+            counter,
+        }) = self.pointers[index + 1].take()
+        else {
+            return;
+        };
+        let separator = self.keys[index].take();
+        self.keys[index..self.n].rotate_left(1);
+        self.pointers[index + 1..=self.n].rotate_left(1);
+        self.n -= 1;
+
+        let left = self.pointers[index].as_mut().unwrap();
+        let left_node = &mut left.child;
+        left_node.keys[left_node.n] = separator;
+        for loc in 0..right_node.n {
+            left_node.keys[left_node.n + 1 + loc] = right_node.keys[loc].take();
+        }
+        if !left_node.leaf {
+            for loc in 0..=right_node.n {
+                left_node.pointers[left_node.n + 1 + loc] = right_node.pointers[loc].take();
+            }
+        }
+        left_node.n += 1 + right_node.n;
+        left.counter += 1 + counter;
+    }
+
+    // replaces an internal root that has run out of keys with its only child
+    pub fn collapse_root(&mut self) {
+        while !self.leaf && self.n == 0 {
+            match self.pointers[0].take() {
+                Some(pointer) => *self = *pointer.child,
+                None => self.leaf = true,
             }
         }
-        output
     }
 }
@@ -0,0 +1,42 @@
+use crate::{methods::cursor::CursorMut, IndexTreeMap};
+
+//Extract If
+// walks the map with a mutable cursor, so an entry stays in the tree until the predicate has
+// returned for it, and only the nodes that lose entries are rebalanced
+pub struct ExtractIf<'a, K, V, F> {
+    cursor: CursorMut<'a, K, V>,
+    pred: F,
+}
+
+impl<'a, K, V, F> ExtractIf<'a, K, V, F>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    pub fn new(tree: &'a mut IndexTreeMap<K, V>, pred: F) -> ExtractIf<'a, K, V, F> {
+        ExtractIf {
+            cursor: tree.cursor_front_mut(),
+            pred,
+        }
+    }
+}
+
+impl<'a, K, V, F> Iterator for ExtractIf<'a, K, V, F>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (key, value) = self.cursor.current_mut()?;
+            if (self.pred)(key, value) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.cursor.size - self.cursor.index))
+    }
+}
@@ -1,7 +1,10 @@
-use crate::stc::Node;
-use std::cmp::Ordering::{Equal, Greater, Less};
+use crate::{stc::Node, KEY_ARRAY, POINTER_ARRAY};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering::{Equal, Greater, Less},
+};
 
-impl<K: Clone + Ord, V: Clone> Node<K, V> {
+impl<K: Ord, V> Node<K, V> {
     pub fn binary_search(&self, key: &K) -> Result<usize, usize> {
         if self.n == 0 {
             Err(0)
@@ -59,6 +62,61 @@ impl<K: Clone + Ord, V: Clone> Node<K, V> {
     }
 }
 
+impl<K: Ord, V> Node<K, V> {
+    // Ok(index) if the key is in the tree, otherwise Err(index) where the key would be inserted
+    pub fn rank<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = self;
+        let mut offset = 0;
+        'descend: loop {
+            for index in 0..POINTER_ARRAY {
+                let counter = node.pointers[index].as_ref().map_or(0, |p| p.counter);
+                if index < KEY_ARRAY {
+                    if let Some(item) = &node.keys[index] {
+                        match key.cmp((*item.key).borrow()) {
+                            Less => {}
+                            Equal => return Ok(offset + counter),
+                            Greater => {
+                                offset += counter + 1;
+                                continue;
+                            }
+                        }
+                    }
+                }
+                match &node.pointers[index] {
+                    Some(pointer) => {
+                        node = &pointer.child;
+                        continue 'descend;
+                    }
+                    None => return Err(offset),
+                }
+            }
+            return Err(offset);
+        }
+    }
+
+    // Ok(index) if the key is one of this node's keys, otherwise Err(index) of the pointer to follow
+    pub fn search_keys<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        for index in 0..self.n {
+            if let Some(item) = &self.keys[index] {
+                match key.cmp((*item.key).borrow()) {
+                    Less => return Err(index),
+                    Equal => return Ok(index),
+                    Greater => {}
+                }
+            }
+        }
+        Err(self.n)
+    }
+}
+
 // pub fn binary_search(arr: &[usize], key: &usize) -> Result<usize, usize> {
 //     if arr.is_empty() {
 //         Err(0)
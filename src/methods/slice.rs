@@ -0,0 +1,385 @@
+use std::ops::RangeBounds;
+
+use crate::{
+    methods::range::{index_range_from_bounds, IndexTreeRange, IndexTreeSetRange},
+    IndexTreeMap,
+};
+
+//Slice
+#[derive(Debug)]
+pub struct IndexTreeSlice<'a, K, V> {
+    pub tree: &'a IndexTreeMap<K, V>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<'a, K, V> Clone for IndexTreeSlice<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for IndexTreeSlice<'a, K, V> {}
+
+impl<'a, K, V> IndexTreeSlice<'a, K, V> {
+    /// Returns the number of entries in the slice.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let page = map.slice(2..8);
+    /// assert_eq!(page.len(), 6);
+    /// assert_eq!(map.slice(8..).len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the slice contains no entries.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// assert!(map.slice(4..4).is_empty());
+    /// assert!(!map.slice(4..5).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the key-value pair at the given index of the slice.
+    ///
+    /// The index is relative to the start of the slice, not to the start of the map.
+    /// Returns `None` if the index is not smaller than the length of the slice.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let page = map.slice(2..8);
+    /// assert_eq!(page.get(0), Some((&20, &2)));
+    /// assert_eq!(page.get(5), Some((&70, &7)));
+    /// assert_eq!(page.get(6), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<(&'a K, &'a V)> {
+        if index < self.len() {
+            self.tree.root.get_from_index(self.start + index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first key-value pair of the slice, or `None` if the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// assert_eq!(map.slice(2..8).first(), Some((&20, &2)));
+    /// assert_eq!(map.slice(2..2).first(), None);
+    /// ```
+    pub fn first(&self) -> Option<(&'a K, &'a V)> {
+        self.get(0)
+    }
+
+    /// Returns the last key-value pair of the slice, or `None` if the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// assert_eq!(map.slice(2..8).last(), Some((&70, &7)));
+    /// assert_eq!(map.slice(2..2).last(), None);
+    /// ```
+    pub fn last(&self) -> Option<(&'a K, &'a V)> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns a view of the entries of this slice between two index positions.
+    ///
+    /// The range is relative to the start of this slice, not to the start of the map,
+    /// and its end is clamped to the length of this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let page = map.slice(2..8);
+    /// let sub_page = page.slice(1..3);
+    /// assert_eq!(sub_page.len(), 2);
+    /// assert_eq!(sub_page.get(0), Some((&30, &3)));
+    /// assert_eq!(page.slice(4..100).len(), 2);
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeSlice<'a, K, V> {
+        let (start, end) = index_range_from_bounds(&range, self.len());
+        IndexTreeSlice {
+            tree: self.tree,
+            start: self.start + start,
+            end: self.start + end,
+        }
+    }
+
+    /// Constructs an iterator over the entries of the slice in key order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeMap;
+    ///
+    /// let mut map = IndexTreeMap::new();
+    /// for key in 0..10 {
+    ///     map.insert(key * 10, key);
+    /// }
+    ///
+    /// let page = map.slice(2..5);
+    /// let keys: Vec<_> = page.iter().map(|(key, _)| *key).collect();
+    /// assert_eq!(keys, vec![20, 30, 40]);
+    /// ```
+    pub fn iter(&self) -> IndexTreeRange<'a, K, V> {
+        IndexTreeRange::new(&self.tree.root, self.tree.size, self.start, self.end)
+    }
+}
+
+impl<'a, K, V> IntoIterator for IndexTreeSlice<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = IndexTreeRange<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//Set Slice
+#[derive(Debug)]
+pub struct IndexTreeSetSlice<'a, K> {
+    pub slice: IndexTreeSlice<'a, K, ()>,
+}
+
+impl<'a, K> Clone for IndexTreeSetSlice<'a, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K> Copy for IndexTreeSetSlice<'a, K> {}
+
+impl<'a, K> IndexTreeSetSlice<'a, K> {
+    /// Returns the number of keys in the slice.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let page = set.slice(2..8);
+    /// assert_eq!(page.len(), 6);
+    /// assert_eq!(set.slice(8..).len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    /// Returns `true` if the slice contains no keys.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// assert!(set.slice(4..4).is_empty());
+    /// assert!(!set.slice(4..5).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
+    }
+
+    /// Returns the key at the given index of the slice.
+    ///
+    /// The index is relative to the start of the slice, not to the start of the set.
+    /// Returns `None` if the index is not smaller than the length of the slice.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let page = set.slice(2..8);
+    /// assert_eq!(page.get(0), Some(&20));
+    /// assert_eq!(page.get(5), Some(&70));
+    /// assert_eq!(page.get(6), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&'a K> {
+        self.slice.get(index).map(|(key, _)| key)
+    }
+
+    /// Returns the first key of the slice, or `None` if the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// assert_eq!(set.slice(2..8).first(), Some(&20));
+    /// assert_eq!(set.slice(2..2).first(), None);
+    /// ```
+    pub fn first(&self) -> Option<&'a K> {
+        self.slice.first().map(|(key, _)| key)
+    }
+
+    /// Returns the last key of the slice, or `None` if the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// assert_eq!(set.slice(2..8).last(), Some(&70));
+    /// assert_eq!(set.slice(2..2).last(), None);
+    /// ```
+    pub fn last(&self) -> Option<&'a K> {
+        self.slice.last().map(|(key, _)| key)
+    }
+
+    /// Returns a view of the keys of this slice between two index positions.
+    ///
+    /// The range is relative to the start of this slice, not to the start of the set,
+    /// and its end is clamped to the length of this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let page = set.slice(2..8);
+    /// let sub_page = page.slice(1..3);
+    /// assert_eq!(sub_page.len(), 2);
+    /// assert_eq!(sub_page.get(0), Some(&30));
+    /// assert_eq!(page.slice(4..100).len(), 2);
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> IndexTreeSetSlice<'a, K> {
+        IndexTreeSetSlice {
+            slice: self.slice.slice(range),
+        }
+    }
+
+    /// Constructs an iterator over the keys of the slice in order.
+    ///
+    /// # Example
+    ///
+    /// Basic usage:
+    /// ```rust
+    /// use indextreemap::IndexTreeSet;
+    ///
+    /// let mut set = IndexTreeSet::new();
+    /// for key in 0..10 {
+    ///     set.insert(key * 10);
+    /// }
+    ///
+    /// let page = set.slice(2..5);
+    /// let keys: Vec<_> = page.iter().copied().collect();
+    /// assert_eq!(keys, vec![20, 30, 40]);
+    /// ```
+    pub fn iter(&self) -> IndexTreeSetRange<'a, K> {
+        IndexTreeSetRange {
+            range: self.slice.iter(),
+        }
+    }
+}
+
+impl<'a, K> IntoIterator for IndexTreeSetSlice<'a, K> {
+    type Item = &'a K;
+    type IntoIter = IndexTreeSetRange<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -1,229 +1,120 @@
-use std::cmp::Ordering::{Equal, Greater, Less};
-
 use crate::{
     stc::{Node, Output, Pointer},
-    KEY_ARRAY, POINTER_ARRAY,
+    KEY_ARRAY, MIN_KEYS,
 };
 
-impl<K: Ord + Clone, V: Clone> Node<K, V> {
-    pub fn split_off(&mut self, key: &K) -> Option<Pointer<K, V>> {
-        for (index, item) in self.keys.iter().enumerate() {
-            if let Some(item) = item {
-                match key.cmp(&item.key) {
-                    Less => {
-                        let mut output = self.split_at_index(index);
-
-                        if let Some(pointer) = self.pointers[index].as_mut() {
-                            output.pointers[0] = pointer.child.split_off(key);
-                            pointer.counter = pointer.child.size();
-                            if pointer.child.is_empty() {
-                                pointer.child.fill_empty_node()
-                            }
-                        } else {
-                            output.fill_first_pointer()
-                        }
-                        self.fill_pointers();
-                        output.fill_pointers();
-
-                        output.n = output.keys.iter().filter(|item| item.is_some()).count();
-                        let size = output.size();
-                        return Some(Pointer {
-                            child: output,
-                            counter: size,
-                        });
-                    }
-                    Equal => {
-                        let mut output = self.split_at_index(index);
-                        output.fill_first_pointer();
-                        output.n = output.keys.iter().filter(|item| item.is_some()).count();
-
-                        self.fill_pointers();
-                        output.fill_pointers();
-
-                        let size = output.size();
-                        return Some(Pointer {
-                            child: output,
-                            counter: size,
-                        });
-                    }
-                    Greater => continue,
-                }
-            } else {
-                let mut output = self.split_at_index(index);
-
-                if let Some(pointer) = self.pointers[index].as_mut() {
-                    output.pointers[0] = pointer.child.split_off(key);
-                    pointer.counter = pointer.child.size();
-                    if pointer.child.is_empty() {
-                        pointer.child.fill_empty_node()
-                    }
-                } else {
-                    output.fill_first_pointer()
-                }
-                self.fill_pointers();
-                output.fill_pointers();
+impl<K, V> Node<K, V> {
+    // cuts the subtree in two by rank: this node keeps the first `index` items and the returned node,
+    // of the same height, takes the rest. counters stay exact, but the nodes along the cut may be left
+    // with too few keys until `fix_right_border` and `fix_left_border` repair them
+    pub fn split_off_at_index(&mut self, mut index: usize) -> Box<Node<K, V>> {
+        let mut right = Node::new();
+        right.leaf = self.leaf;
 
-                output.n = output.keys.iter().filter(|item| item.is_some()).count();
-                let size = output.size();
-                return Some(Pointer {
-                    child: output,
-                    counter: size,
-                });
+        if self.leaf {
+            let index = index.min(self.n);
+            for loc in index..self.n {
+                right.keys[loc - index] = self.keys[loc].take();
             }
+            right.n = self.n - index;
+            self.n = index;
+            return right;
         }
 
-        None
-    }
-
-    pub fn split_off_at_index(&mut self, mut index: usize) -> Option<Pointer<K, V>> {
-        if self.leaf {
-            let mut output = self.split_at_index(index);
-            output.n = output.keys.iter().filter(|item| item.is_some()).count();
-            let size = output.size();
-            return Some(Pointer {
-                child: output,
-                counter: size,
-            });
-        } else {
-            for loc in 0..KEY_ARRAY {
-                if self.pointers[loc].is_some() {
-                    if index < self.pointers[loc].as_ref().unwrap().counter {
-                        let mut output = self.split_at_index(loc);
-                        let pointer = self.pointers[loc].as_mut().unwrap();
-                        output.pointers[0] = pointer.child.split_off_at_index(index);
-                        pointer.counter = pointer.child.size();
-                        // if pointer.child.is_empty() {
-                        //     pointer.child.fill_empty_node();
-                        //     output.fill_first_pointer()
-                        // }
-                        // self.fill_pointers();
-                        // output.fill_pointers();
-
-                        output.n = output.keys.iter().filter(|item| item.is_some()).count();
-                        let size = output.size();
-                        return Some(Pointer {
-                            child: output,
-                            counter: size,
-                        });
-                    } else {
-                        index -= self.pointers[loc].as_mut().unwrap().counter
-                    }
-
-                    if index == 0 {
-                        if self.keys[index].is_some() {
-                            let mut output = self.split_at_index(loc);
-                            output.fill_first_pointer();
-                            output.n = output.keys.iter().filter(|item| item.is_some()).count();
-                            self.fill_pointers();
-                            output.fill_pointers();
-
-                            let size = output.size();
-                            return Some(Pointer {
-                                child: output,
-                                counter: size,
-                            });
-                        }
-                    } else {
-                        index -= 1;
-                    }
-                } else {
-                    continue;
-                }
+        let mut loc = 0;
+        while loc < self.n {
+            let counter = self.pointers[loc].as_ref().map_or(0, |p| p.counter);
+            if index <= counter {
+                break;
             }
+            index -= counter + 1;
+            loc += 1;
+        }
 
-            if let Some(pointer) = self.pointers[KEY_ARRAY].as_mut() {
-                if index < pointer.counter {
-                    let output = pointer.child.split_off_at_index(index);
-                    pointer.counter = pointer.child.size();
+        let pointer = self.pointers[loc].as_mut().unwrap();
+        let index = index.min(pointer.counter);
+        right.pointers[0] = Some(Pointer {
+            child: pointer.child.split_off_at_index(index),
+            counter: pointer.counter - index,
+        });
+        pointer.counter = index;
 
-                    return output;
-                } else {
-                    return None;
-                }
-            }
+        for from in loc..self.n {
+            right.keys[from - loc] = self.keys[from].take();
+            right.pointers[from - loc + 1] = self.pointers[from + 1].take();
         }
-
-        None
+        right.n = self.n - loc;
+        self.n = loc;
+        right
     }
 
-    pub fn fill_empty_root(&mut self) {
-        if self.is_empty() {
-            if let Some(pointer) = self.pointers[0].as_mut() {
-                self.keys = pointer.child.take_keys();
-                self.n = pointer.child.n;
-                self.leaf = pointer.child.leaf;
-                self.pointers = pointer.child.take_pointers();
-            }
-        }
+    // repairs the right edge of a tree that was cut by `split_off_at_index`
+    pub fn fix_right_border(&mut self) {
+        self.collapse_root();
+        self.stock_right_border();
+        self.collapse_root();
     }
 
-    pub fn fill_pointers(&mut self) {
-        for index in 0..POINTER_ARRAY {
-            if let Some(pointer) = self.pointers[index].as_mut() {
-                if pointer.counter == 0 {
-                    self.pointers[index] = None;
-                    continue;
-                } else if pointer.child.is_empty() {
-                    pointer.child.fill_empty_node()
-                }
-            }
-        }
+    // repairs the left edge of a tree that was cut by `split_off_at_index`
+    pub fn fix_left_border(&mut self) {
+        self.collapse_root();
+        self.stock_left_border();
+        self.collapse_root();
     }
 
-    pub fn fill_empty_node(&mut self) {
-        if self.is_empty() {
-            if let Some(pointer) = self.pointers[0].as_mut() {
-                if let Some(key) = pointer.child.take_last_key() {
-                    self.keys[0] = Some(key);
-                    self.n += 1;
-                    pointer.counter -= 1
-                }
-            }
+    // walks down the right edge, leaving each child on it with more than MIN_KEYS keys
+    // so that it can give one up to its own child further down
+    fn stock_right_border(&mut self) {
+        if self.leaf {
+            return;
+        }
+        if self.n > 0 {
+            self.balance_children(self.n - 1);
+        }
+        let last = self.n;
+        if let Some(pointer) = self.pointers[last].as_mut() {
+            pointer.child.stock_right_border();
         }
     }
 
-    pub fn fill_first_pointer(&mut self) {
-        if let Some(pointer) = self.pointers[1].as_mut() {
-            if pointer.child.n < KEY_ARRAY - 1 {
-                if let Some(key) = self.keys[0].take() {
-                    // self.n -= 1;
-                    pointer.child.keys.rotate_right(1);
-                    pointer.child.pointers.rotate_right(1);
-                    pointer.child.keys[0] = Some(key);
-                    pointer.counter += 1;
-                    pointer.child.n += 1;
-                    pointer.child.fill_first_pointer();
-                }
-            }
-            for index in 0..(KEY_ARRAY - 1) {
-                self.keys[index] = self.keys[index + 1].take();
-                self.pointers[index] = self.pointers[index + 1].take()
-            }
-            self.pointers[KEY_ARRAY - 1] = self.pointers[KEY_ARRAY].take()
+    fn stock_left_border(&mut self) {
+        if self.leaf {
+            return;
+        }
+        if self.n > 0 {
+            self.balance_children(0);
+        }
+        if let Some(pointer) = self.pointers[0].as_mut() {
+            pointer.child.stock_left_border();
         }
     }
 
-    pub fn split_at_index(&mut self, index: usize) -> Box<Node<K, V>> {
-        let mut new_node: Box<Node<K, V>> = Node::new();
-        new_node.leaf = self.leaf;
+    // evens out the children at `index` and `index + 1` when either has MIN_KEYS keys or fewer:
+    // they are merged if they fit in one node, otherwise keys are rotated towards the smaller one
+    // until it has more than MIN_KEYS keys
+    pub fn balance_children(&mut self, index: usize) {
+        let len = |pointer: &Option<Pointer<K, V>>| pointer.as_ref().map_or(0, |p| p.child.n);
+        let left = len(&self.pointers[index]);
+        let right = len(&self.pointers[index + 1]);
 
-        let mut count = 0;
-
-        for loc in 0..KEY_ARRAY {
-            if loc >= index {
-                new_node.keys[count] = self.keys[loc].take();
-                new_node.pointers[count + 1] = self.pointers[loc + 1].take();
-                count += 1
+        if left > MIN_KEYS && right > MIN_KEYS {
+            return;
+        }
+        if left + right + 1 < KEY_ARRAY {
+            self.merge_children(index);
+        } else if left <= MIN_KEYS {
+            for _ in left..=MIN_KEYS {
+                self.rotate_left(index);
+            }
+        } else {
+            for _ in right..=MIN_KEYS {
+                self.rotate_right(index);
             }
         }
-
-        self.n = self.keys.iter().filter(|item| item.is_some()).count();
-
-        new_node
     }
 }
 
-impl<K: Clone, V: Clone> Node<K, V> {
+impl<K, V> Node<K, V> {
     pub fn split_root(&mut self) {
         let new_root_key = self.keys[KEY_ARRAY / 2].take();
 
@@ -24,23 +24,43 @@ impl<K, V> Node<K, V> {
     }
 }
 
-impl<K: Ord, V> Node<K, V> {
+impl<K, V> Node<K, V> {
+    pub fn take_first_key(&mut self) -> Option<Box<Item<K, V>>> {
+        if self.n == 0 && self.leaf {
+            return None;
+        }
+        if !self.leaf {
+            if let Some(pointer) = self.pointers[0].as_mut() {
+                if let Some(item) = pointer.child.take_first_key() {
+                    pointer.counter -= 1;
+                    self.rebalance_child(0);
+                    return Some(item);
+                }
+            }
+        }
+        match self.n {
+            0 => None,
+            _ => self.remove_key(0),
+        }
+    }
+
     pub fn take_last_key(&mut self) -> Option<Box<Item<K, V>>> {
-        for index in (0..KEY_ARRAY).rev() {
-            match &self.keys[index] {
-                Some(_) => {
-                    let output = self.keys[index].take();
-                    if output.is_some() {
-                        self.n -= 1;
-                    }
-                    if self.leaf {
-                        self.replace_removed_key(index)
-                    }
-                    return output;
+        if !self.leaf {
+            let index = self.n;
+            if let Some(pointer) = self.pointers[index].as_mut() {
+                if let Some(item) = pointer.child.take_last_key() {
+                    pointer.counter -= 1;
+                    self.rebalance_child(index);
+                    return Some(item);
                 }
-                None => continue,
             }
         }
-        None
+        match self.n {
+            0 => None,
+            _ => {
+                self.n -= 1;
+                self.keys[self.n].take()
+            }
+        }
     }
 }
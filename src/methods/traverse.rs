@@ -0,0 +1,294 @@
+use std::{collections::VecDeque, iter::FusedIterator};
+
+use crate::stc::{Item, Node};
+
+// a pending position in an in-order walk, either a single item or a whole subtree with its size
+pub enum Slot<N, I> {
+    Item(I),
+    Child(N, usize),
+}
+
+// a way of holding a node (shared, mutable or owned) that a walk can open up into its slots
+pub trait Expand: Sized {
+    type Item;
+
+    // the items and non-empty children of the node, in order
+    fn expand(self) -> impl DoubleEndedIterator<Item = Slot<Self, Self::Item>>;
+}
+
+impl<'a, K, V> Expand for &'a Node<K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn expand(self) -> impl DoubleEndedIterator<Item = Slot<Self, Self::Item>> {
+        let (last, pointers) = self.pointers.split_last().unwrap();
+        interleave(
+            pointers.iter(),
+            last,
+            self.keys.iter(),
+            |pointer| {
+                let pointer = pointer.as_ref().filter(|p| p.counter > 0)?;
+                Some(Slot::Child(&*pointer.child, pointer.counter))
+            },
+            |item| {
+                let item = item.as_deref()?;
+                Some(Slot::Item((&*item.key, &*item.value)))
+            },
+        )
+    }
+}
+
+impl<'a, K, V> Expand for &'a mut Node<K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn expand(self) -> impl DoubleEndedIterator<Item = Slot<Self, Self::Item>> {
+        let Node { keys, pointers, .. } = self;
+        let (last, pointers) = pointers.split_last_mut().unwrap();
+        interleave(
+            pointers.iter_mut(),
+            last,
+            keys.iter_mut(),
+            |pointer| {
+                let pointer = pointer.as_mut().filter(|p| p.counter > 0)?;
+                Some(Slot::Child(&mut *pointer.child, pointer.counter))
+            },
+            |item| {
+                let Item { key, value } = item.as_deref_mut()?;
+                Some(Slot::Item((&**key, &mut **value)))
+            },
+        )
+    }
+}
+
+impl<K, V> Expand for Box<Node<K, V>> {
+    type Item = (K, V);
+
+    fn expand(self) -> impl DoubleEndedIterator<Item = Slot<Self, Self::Item>> {
+        let Node { keys, pointers, .. } = *self;
+        let mut pointers = pointers.into_iter();
+        let last = pointers.next_back().flatten();
+        interleave(
+            pointers,
+            last,
+            keys.into_iter(),
+            |pointer| {
+                let pointer = pointer.filter(|p| p.counter > 0)?;
+                Some(Slot::Child(pointer.child, pointer.counter))
+            },
+            |item| {
+                let item = item?;
+                Some(Slot::Item((*item.key, *item.value)))
+            },
+        )
+    }
+}
+
+// lays out the children and keys of a node as child, key, child, ..., key, last child,
+// leaving out the children and keys that `child` and `item` map to `None`
+fn interleave<P, I, S>(
+    pointers: impl DoubleEndedIterator<Item = P> + ExactSizeIterator,
+    last: P,
+    keys: impl DoubleEndedIterator<Item = I> + ExactSizeIterator,
+    child: impl Fn(P) -> Option<S> + Copy,
+    item: impl Fn(I) -> Option<S>,
+) -> impl DoubleEndedIterator<Item = S> {
+    pointers
+        .zip(keys)
+        .flat_map(move |(pointer, key)| [child(pointer), item(key)])
+        .chain([child(last)])
+        .flatten()
+}
+
+// the remaining slots of a walk through the tree, nearest first
+pub struct Traversal<N: Expand> {
+    pub slots: VecDeque<Slot<N, N::Item>>,
+}
+
+impl<N: Expand> Traversal<N> {
+    pub fn new(root: N, size: usize) -> Traversal<N> {
+        let mut slots = VecDeque::new();
+        if size > 0 {
+            slots.push_back(Slot::Child(root, size));
+        }
+        Traversal { slots }
+    }
+
+    pub fn next_front(&mut self) -> Option<N::Item> {
+        self.next(false)
+    }
+
+    pub fn next_back(&mut self) -> Option<N::Item> {
+        self.next(true)
+    }
+
+    // skips `n` items from the front, stepping over whole subtrees by their counter
+    pub fn skip_front(&mut self, n: usize) {
+        self.skip(n, false)
+    }
+
+    // skips `n` items from the back, stepping over whole subtrees by their counter
+    pub fn skip_back(&mut self, n: usize) {
+        self.skip(n, true)
+    }
+
+    fn next(&mut self, back: bool) -> Option<N::Item> {
+        loop {
+            match self.pop(back)? {
+                Slot::Item(item) => return Some(item),
+                Slot::Child(node, _) => self.push(node, back),
+            }
+        }
+    }
+
+    fn skip(&mut self, mut n: usize, back: bool) {
+        while n > 0 {
+            match self.pop(back) {
+                None => return,
+                Some(Slot::Item(_)) => n -= 1,
+                Some(Slot::Child(node, counter)) => {
+                    if counter <= n {
+                        n -= counter
+                    } else {
+                        self.push(node, back)
+                    }
+                }
+            }
+        }
+    }
+
+    fn pop(&mut self, back: bool) -> Option<Slot<N, N::Item>> {
+        match back {
+            false => self.slots.pop_front(),
+            true => self.slots.pop_back(),
+        }
+    }
+
+    // replaces a subtree taken from one end of the walk with its slots
+    fn push(&mut self, node: N, back: bool) {
+        match back {
+            false => {
+                for slot in node.expand().rev() {
+                    self.slots.push_front(slot);
+                }
+            }
+            true => self.slots.extend(node.expand()),
+        }
+    }
+}
+
+// the items at indexes [index, end) of a walk over the tree, which started at index `start`;
+// every iterator over the tree is this walk, or an adapter around it
+pub struct Walk<N: Expand> {
+    pub traversal: Traversal<N>,
+    pub start: usize,
+    pub index: usize,
+    pub end: usize,
+}
+
+impl<N: Expand> Walk<N> {
+    // walks the items at indexes [start, end) of the tree of `size` items below `root`
+    pub fn new(root: N, size: usize, start: usize, end: usize) -> Walk<N> {
+        let mut traversal = Traversal::new(root, size);
+        traversal.skip_front(start);
+        traversal.skip_back(size.saturating_sub(end));
+        Walk {
+            traversal,
+            start,
+            index: start,
+            end,
+        }
+    }
+}
+
+impl<N: Expand> Iterator for Walk<N> {
+    type Item = N::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.index += 1;
+            return self.traversal.next_front();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.end - self.index {
+            self.traversal.slots.clear();
+            self.index = self.end;
+            return None;
+        }
+        self.traversal.skip_front(n);
+        self.index += n;
+        self.next()
+    }
+}
+
+impl<N: Expand> DoubleEndedIterator for Walk<N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.end {
+            self.end -= 1;
+            return self.traversal.next_back();
+        }
+        None
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.end - self.index {
+            self.traversal.slots.clear();
+            self.end = self.index;
+            return None;
+        }
+        self.traversal.skip_back(n);
+        self.end -= n;
+        self.next_back()
+    }
+}
+
+impl<N: Expand> ExactSizeIterator for Walk<N> {}
+
+impl<N: Expand> FusedIterator for Walk<N> {}
+
+// declares an iterator that maps each item of an inner iterator, handing lengths and jumps to it
+macro_rules! adapter {
+    ($name:ident<$($param:tt),*>, $field:ident: $inner:ty, $item:ty, |$pat:pat_param| $map:expr) => {
+        pub struct $name<$($param),*> {
+            pub $field: $inner,
+        }
+
+        impl<$($param),*> Iterator for $name<$($param),*> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.$field.next().map(|$pat| $map)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.$field.size_hint()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.$field.nth(n).map(|$pat| $map)
+            }
+        }
+
+        impl<$($param),*> DoubleEndedIterator for $name<$($param),*> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.$field.next_back().map(|$pat| $map)
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.$field.nth_back(n).map(|$pat| $map)
+            }
+        }
+
+        impl<$($param),*> ExactSizeIterator for $name<$($param),*> {}
+
+        impl<$($param),*> std::iter::FusedIterator for $name<$($param),*> {}
+    };
+}
+
+pub(crate) use adapter;
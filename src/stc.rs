@@ -8,7 +8,7 @@ pub enum Output<K, V> {
     #[default]
     Null,
     KeyIsNew,
-    KeyExists,
+    KeyExists(V),
     NewKeyPointer(Option<Box<Item<K, V>>>, Option<Pointer<K, V>>),
 }
 
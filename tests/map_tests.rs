@@ -1,8 +1,9 @@
 #[cfg(test)]
 pub mod tests {
 
-    use indextreemap::IndexTreeMap;
+    use indextreemap::{IndexTreeMap, Pos};
     use sha2::{Digest, Sha256};
+    use std::ops::Bound::{Included, Unbounded};
 
     const SCOPE: usize = 2_000;
 
@@ -158,29 +159,26 @@ pub mod tests {
         }
     }
 
-    // #[test]
-    // fn string_remove_index() {
-    //     let mut tree = IndexTreeMap::new();
-    //     for i in 0..SCOPE {
-    //         tree.insert(hash(i.to_le_bytes().as_slice()), i)
-    //     }
-
-    //     // println!("{tree:#?}");
-
-    //     for i in (0..SCOPE).rev() {
-    //         let tree_copy = tree.clone();
-    //         let key_value: (&String, &usize) = tree_copy.get_key_value_from_index(i).unwrap();
+    #[test]
+    fn string_remove_index() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(hash(i.to_le_bytes().as_slice()), i)
+        }
 
-    //         tree.remove_from_index(i);
+        for i in (0..SCOPE).rev() {
+            let tree_copy = tree.clone();
+            let key_value: (&String, &usize) = tree_copy.get_key_value_from_index(i).unwrap();
 
-    //         println!("{tree:#?}");
+            tree.remove_from_index(i);
 
-    //         for (key, value) in tree.iter() {
-    //             assert_ne!(key, key_value.0);
-    //             assert_ne!(value, key_value.1)
-    //         }
-    //     }
-    // }
+            for (key, value) in tree.iter() {
+                assert_ne!(key, key_value.0);
+                assert_ne!(value, key_value.1)
+            }
+        }
+        assert!(tree.is_empty());
+    }
 
     #[test]
     fn string_replace() {
@@ -257,6 +255,40 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn string_borrowed_lookups() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(hash(i.to_le_bytes().as_slice()), i)
+        }
+
+        let key = hash((SCOPE / 2).to_le_bytes().as_slice());
+        let key: &str = key.as_str();
+        assert!(tree.contains_key(key));
+        assert_eq!(tree.get(key), Some(&(SCOPE / 2)));
+        assert_eq!(
+            tree.get_key_value(key).map(|(_, value)| *value),
+            Some(SCOPE / 2)
+        );
+        *tree.get_mut(key).unwrap() += 1;
+        assert_eq!(tree.replace(key, 0), Some(SCOPE / 2 + 1));
+
+        let index = tree.get_index_from_key(key).unwrap();
+        assert_eq!(
+            tree.range::<str, _>((Included(key), Unbounded)).start,
+            index
+        );
+
+        let split_tree = tree.split_off(key);
+        assert_eq!(split_tree.get(key), Some(&0));
+        assert_eq!(tree.len() + split_tree.len(), SCOPE);
+        assert_eq!(tree.len(), index);
+
+        let mut tree = split_tree;
+        assert_eq!(tree.remove(key).map(|(_, value)| value), Some(0));
+        assert!(!tree.contains_key(key));
+    }
+
     //* I32 TESTS *//
     // * * Expansive Testing has a time complexity of O(SCOPE * SCOPE)
     #[test]
@@ -565,6 +597,867 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn usize_iter_index() {
+        let mut tree = IndexTreeMap::new();
+        for i in (0..SCOPE).rev() {
+            tree.insert(i * 2, i)
+        }
+
+        let mut iter = tree.iter();
+        let mut keys = tree.keys();
+        let mut values = tree.values();
+        for index in 0..SCOPE {
+            assert_eq!(iter.index, index);
+            assert_eq!(iter.next(), Some((&(index * 2), &index)));
+            assert_eq!(keys.next(), Some(&(index * 2)));
+            assert_eq!(values.next(), Some(&index));
+        }
+        assert_eq!(iter.index, SCOPE);
+        assert_eq!(iter.next(), None);
+        assert_eq!(keys.next(), None);
+        assert_eq!(values.next(), None);
+    }
+
+    #[test]
+    fn usize_iter_double_ended() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+        let expected: Vec<usize> = (0..SCOPE).collect();
+
+        assert!(tree.keys().rev().eq(expected.iter().rev()));
+        assert!(tree.values().rev().eq(expected.iter().rev()));
+        assert_eq!(tree.iter().len(), SCOPE);
+        assert_eq!(tree.range_by_index(10..20).len(), 10);
+
+        for step in [0, 1, 7, 64, SCOPE / 3, SCOPE] {
+            let mut iter = tree.keys();
+            let mut other = expected.iter();
+            loop {
+                let front = iter.nth(step);
+                assert_eq!(front, other.nth(step));
+                let back = iter.nth_back(step / 2);
+                assert_eq!(back, other.nth_back(step / 2));
+                assert_eq!(iter.len(), other.len());
+                if front.is_none() && back.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+        }
+
+        let mut range = tree.range_by_index(100..200);
+        assert_eq!(range.next_back(), Some((&199, &199)));
+        assert_eq!(range.nth(10), Some((&110, &110)));
+        assert_eq!(range.nth_back(88), None);
+
+        let mut into_iter = tree.clone().into_iter();
+        assert_eq!(into_iter.nth_back(9), Some((SCOPE - 10, SCOPE - 10)));
+        assert_eq!(into_iter.nth(4), Some((4, 4)));
+        assert_eq!(into_iter.len(), SCOPE - 15);
+
+        for (_, value) in tree.iter_mut().rev().step_by(2) {
+            *value = 0;
+        }
+        assert_eq!(tree.get(&(SCOPE - 1)), Some(&0));
+        assert_eq!(tree.get(&(SCOPE - 2)), Some(&(SCOPE - 2)));
+    }
+
+    #[test]
+    fn usize_iter_mut() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        for (key, value) in tree.iter_mut() {
+            *value += key;
+        }
+        for value in tree.values_mut().rev().take(10) {
+            *value = 0;
+        }
+        for (_, value) in tree.range_mut(100..200) {
+            *value = 1;
+        }
+        let mut range = tree.range_mut_by_index(SCOPE / 2..);
+        assert_eq!(range.len(), SCOPE - SCOPE / 2);
+        *range.next().unwrap().1 = 2;
+
+        for (key, value) in tree.iter() {
+            let expected = match *key {
+                100..=199 => 1,
+                key if key == SCOPE / 2 => 2,
+                key if key >= SCOPE - 10 => 0,
+                key => key * 2,
+            };
+            assert_eq!(*value, expected);
+        }
+    }
+
+    #[test]
+    fn usize_iter_from() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2, i)
+        }
+
+        for key in [
+            0,
+            1,
+            2,
+            SCOPE - 1,
+            SCOPE,
+            SCOPE * 2 - 2,
+            SCOPE * 2 - 1,
+            SCOPE * 3,
+        ] {
+            let from = key.div_ceil(2).min(SCOPE);
+            let iter = tree.iter_from_key(&key);
+            assert_eq!(iter.index, from);
+            assert!(iter.map(|(key, _)| *key).eq((from..SCOPE).map(|i| i * 2)));
+
+            let until = (key / 2 + 1).min(SCOPE);
+            let rev = tree.iter_from_key_rev(&key);
+            assert!(rev.map(|(key, _)| *key).eq((0..until).rev().map(|i| i * 2)));
+        }
+
+        for index in [0, 1, SCOPE / 2, SCOPE - 1, SCOPE, SCOPE + 5] {
+            let from = index.min(SCOPE);
+            assert!(tree.iter_from_index(index).map(|(_, v)| *v).eq(from..SCOPE));
+            let until = (index + 1).min(SCOPE);
+            assert!(tree
+                .iter_from_index_rev(index)
+                .map(|(_, v)| *v)
+                .eq((0..until).rev()));
+        }
+    }
+
+    #[test]
+    fn usize_range() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2, i)
+        }
+
+        for start in (0..SCOPE * 2).step_by(37) {
+            for end in (start..SCOPE * 2 + 3).step_by(53) {
+                let range = tree.range(start..end);
+                assert_eq!(range.start, start.div_ceil(2));
+                let keys: Vec<usize> = range.map(|(key, _)| *key).collect();
+                let expected: Vec<usize> = (start..end.min(SCOPE * 2))
+                    .filter(|key| key % 2 == 0)
+                    .collect();
+                assert_eq!(keys, expected);
+
+                let keys: Vec<usize> = tree.range(start..=end).map(|(key, _)| *key).collect();
+                let expected: Vec<usize> = (start..=end.min(SCOPE * 2 - 1))
+                    .filter(|key| key % 2 == 0)
+                    .collect();
+                assert_eq!(keys, expected);
+            }
+        }
+
+        assert_eq!(tree.range(..).count(), SCOPE);
+        assert_eq!(tree.range(SCOPE * 2..).count(), 0);
+        assert_eq!(tree.range(..=0).collect::<Vec<_>>(), vec![(&0, &0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn usize_range_inverted() {
+        let mut tree = IndexTreeMap::new();
+        tree.insert(1, 1);
+        let (start, end) = (2, 1);
+        tree.range(start..end);
+    }
+
+    #[test]
+    fn usize_range_by_index() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        for start in (0..SCOPE).step_by(41) {
+            for end in (start..SCOPE + 5).step_by(67) {
+                let keys: Vec<usize> = tree
+                    .range_by_index(start..end)
+                    .map(|(key, _)| *key)
+                    .collect();
+                let expected: Vec<usize> = (start..end.min(SCOPE)).collect();
+                assert_eq!(keys, expected);
+            }
+        }
+
+        assert_eq!(tree.range_by_index(..).count(), SCOPE);
+        assert_eq!(tree.range_by_index(SCOPE..).count(), 0);
+        assert_eq!(tree.range_by_index(SCOPE + 10..).count(), 0);
+    }
+
+    #[test]
+    fn usize_slice() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        let page = tree.slice(100..200);
+        assert_eq!(page.len(), 100);
+        assert_eq!(page.first(), Some((&100, &100)));
+        assert_eq!(page.last(), Some((&199, &199)));
+        assert_eq!(page.get(100), None);
+
+        let sub_page = page.slice(10..=19);
+        assert_eq!(sub_page.len(), 10);
+        for i in 0..10 {
+            assert_eq!(sub_page.get(i), Some((&(110 + i), &(110 + i))));
+        }
+        assert_eq!(
+            sub_page.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            (110..120).collect::<Vec<_>>()
+        );
+
+        let tail = page.slice(90..1_000);
+        assert_eq!(tail.len(), 10);
+        assert!(page.slice(100..).is_empty());
+    }
+
+    #[test]
+    fn usize_entry() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2, i)
+        }
+
+        for i in 0..SCOPE {
+            // odd keys below i * 2 have already been inserted
+            let entry = tree.entry(i * 2);
+            assert_eq!(entry.index(), i * 2);
+            assert_eq!(entry.key(), &(i * 2));
+            *entry.or_insert(0) += 1;
+
+            let entry = tree.entry(i * 2 + 1);
+            assert_eq!(entry.index(), i * 2 + 1);
+            entry.and_modify(|value| *value = 0).or_insert_with(|| i);
+        }
+
+        assert_eq!(tree.len(), SCOPE * 2);
+        for i in 0..SCOPE {
+            assert_eq!(tree.get(&(i * 2)), Some(&(i + 1)));
+            assert_eq!(tree.get(&(i * 2 + 1)), Some(&i));
+        }
+    }
+
+    #[test]
+    fn string_entry() {
+        let mut tree: IndexTreeMap<String, Vec<usize>> = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.entry(hash((i % 100).to_le_bytes().as_slice()))
+                .or_default()
+                .push(i);
+        }
+
+        assert_eq!(tree.len(), 100);
+        for (_, values) in tree.iter() {
+            assert_eq!(values.len(), SCOPE / 100);
+        }
+    }
+
+    #[test]
+    fn usize_rank() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2 + 1, i)
+        }
+
+        for key in 0..=SCOPE * 2 {
+            let below = key / 2;
+            if key % 2 == 1 {
+                assert_eq!(tree.rank(&key), Ok(below));
+                assert_eq!(tree.lower_bound_index(&key), below);
+                assert_eq!(tree.upper_bound_index(&key), below + 1);
+            } else {
+                assert_eq!(tree.rank(&key), Err(below));
+                assert_eq!(tree.lower_bound_index(&key), below);
+                assert_eq!(tree.upper_bound_index(&key), below);
+            }
+        }
+
+        let empty: IndexTreeMap<usize, usize> = IndexTreeMap::new();
+        assert_eq!(empty.rank(&1), Err(0));
+        assert_eq!(empty.upper_bound_index(&1), 0);
+    }
+
+    #[test]
+    fn usize_floor_ceiling() {
+        let mut tree = IndexTreeMap::new();
+        for i in 1..=SCOPE {
+            tree.insert(i * 10, i)
+        }
+
+        for key in 0..SCOPE * 10 + 20 {
+            let below = (key / 10).min(SCOPE);
+            let floor = tree.floor(&key).map(|(index, key, _)| (index, *key));
+            let ceiling = tree.ceiling(&key).map(|(index, key, _)| (index, *key));
+            let predecessor = tree.predecessor(&key).map(|(index, key, _)| (index, *key));
+            let successor = tree.successor(&key).map(|(index, key, _)| (index, *key));
+
+            let at = |count: usize| (count <= SCOPE && count > 0).then(|| (count - 1, count * 10));
+            if key % 10 == 0 && below > 0 && key <= SCOPE * 10 {
+                assert_eq!(floor, at(below));
+                assert_eq!(ceiling, at(below));
+                assert_eq!(predecessor, at(below - 1));
+                assert_eq!(successor, at(below + 1));
+            } else {
+                assert_eq!(floor, at(below));
+                assert_eq!(ceiling, at(below + 1));
+                assert_eq!(predecessor, at(below));
+                assert_eq!(successor, at(below + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn usize_pop_first_last() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        for i in 0..SCOPE / 2 {
+            assert_eq!(tree.pop_first(), Some((i, i)));
+            assert_eq!(tree.pop_last(), Some((SCOPE - 1 - i, SCOPE - 1 - i)));
+            assert_eq!(tree.len(), SCOPE - 2 * (i + 1));
+            if !tree.is_empty() {
+                assert_eq!(tree.get_first_key(), Some(&(i + 1)));
+                assert_eq!(tree.get_last_key(), Some(&(SCOPE - 2 - i)));
+                assert_eq!(
+                    tree.get_index_from_key(&(SCOPE / 2)),
+                    Some(SCOPE / 2 - i - 1)
+                );
+            }
+        }
+        assert_eq!(tree.pop_first(), None);
+        assert_eq!(tree.pop_last(), None);
+        assert_eq!(tree.get_last_key(), None);
+    }
+
+    #[test]
+    fn usize_first_last_entry() {
+        let mut tree = IndexTreeMap::new();
+        assert!(tree.first_entry().is_none());
+        assert!(tree.last_entry().is_none());
+
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        *tree.first_entry().unwrap().get_mut() += SCOPE;
+        assert_eq!(tree.get(&0), Some(&SCOPE));
+
+        let entry = tree.last_entry().unwrap();
+        assert_eq!(entry.index(), SCOPE - 1);
+        assert_eq!(entry.key(), &(SCOPE - 1));
+        assert_eq!(entry.remove(), SCOPE - 1);
+
+        while let Some(entry) = tree.first_entry() {
+            let index = entry.index();
+            let (key, _) = entry.remove_entry();
+            assert_eq!(index, 0);
+            assert!(!tree.contains_key(&key));
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn usize_retain() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        tree.retain(|key, value| {
+            *value += 1;
+            key % 3 == 0
+        });
+
+        assert_eq!(tree.len(), SCOPE.div_ceil(3));
+        for (index, (key, value)) in tree.iter().enumerate() {
+            assert_eq!(*key, index * 3);
+            assert_eq!(*value, index * 3 + 1);
+            assert_eq!(tree.get_index_from_key(key), Some(index));
+        }
+
+        tree.retain(|_, _| false);
+        assert!(tree.is_empty());
+        assert_eq!(tree.get_first_key(), None);
+    }
+
+    #[test]
+    fn usize_extract_if() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        let extracted: Vec<(usize, usize)> = tree.extract_if(|key, _| key % 2 == 1).collect();
+        assert_eq!(extracted.len(), SCOPE / 2);
+        assert!(extracted.iter().all(|(key, _)| key % 2 == 1));
+        assert!(tree.keys().copied().eq((0..SCOPE).step_by(2)));
+
+        // items left unvisited when the iterator is dropped stay in the map
+        let first: Vec<(usize, usize)> = tree.extract_if(|_, _| true).take(10).collect();
+        assert_eq!(
+            first,
+            (0..20).step_by(2).map(|i| (i, i)).collect::<Vec<_>>()
+        );
+        assert_eq!(tree.len(), SCOPE / 2 - 10);
+        assert_eq!(tree.get_first_key(), Some(&20));
+
+        // a panicking predicate keeps the entry it was looking at
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tree.extract_if(|key, _| key % 4 == 0 || panic!("{key}"))
+                .count()
+        }));
+        assert!(result.is_err());
+        assert_eq!(tree.len(), SCOPE / 2 - 11);
+        assert_eq!(tree.get_first_key(), Some(&22));
+        assert!(tree.keys().copied().eq((22..SCOPE).step_by(2)));
+    }
+
+    #[test]
+    fn usize_into_iter() {
+        let mut tree = IndexTreeMap::new();
+        for i in (0..SCOPE).rev() {
+            tree.insert(i, i.to_string())
+        }
+
+        for (key, value) in &mut tree {
+            value.push_str(&key.to_string());
+        }
+        for (index, (key, value)) in (&tree).into_iter().enumerate() {
+            assert_eq!(*key, index);
+            assert_eq!(value, &format!("{index}{index}"));
+        }
+
+        assert!(tree.clone().into_keys().eq(0..SCOPE));
+        assert!(tree
+            .clone()
+            .into_values()
+            .eq((0..SCOPE).map(|i| format!("{i}{i}"))));
+
+        let mut iter = tree.into_iter();
+        assert_eq!(iter.next(), Some((0, "00".to_string())));
+        assert_eq!(iter.count(), SCOPE - 1);
+    }
+
+    #[test]
+    fn usize_cursor() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 3, i)
+        }
+
+        let mut cursor = tree.cursor_front();
+        for i in 0..SCOPE {
+            assert_eq!(cursor.index(), Some(i));
+            assert_eq!(cursor.current(), Some((&(i * 3), &i)));
+            cursor.move_next();
+        }
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some((&0, &0)));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(SCOPE - 1));
+
+        assert!(!cursor.seek_key(&301));
+        assert_eq!(cursor.current(), Some((&303, &101)));
+        assert_eq!(cursor.peek_prev(), Some((&300, &100)));
+        cursor.seek_index(SCOPE * 2);
+        assert_eq!(cursor.index(), None);
+
+        // interleave a new key after every existing one, and drop every second original
+        let mut cursor = tree.cursor_front_mut();
+        while let Some((&key, _)) = cursor.current() {
+            assert!(cursor.insert_after(key + 1, 0).is_ok());
+            assert!(cursor.insert_before(key + 2, 0).is_err());
+            if key % 2 == 0 {
+                cursor.remove_current();
+            } else {
+                cursor.move_next();
+            }
+            cursor.move_next();
+        }
+        assert!(cursor.insert_before(SCOPE * 3, 0).is_ok());
+        assert!(cursor.insert_after(0, 0).is_ok());
+        assert_eq!(cursor.index(), None);
+        drop(cursor);
+
+        assert_eq!(tree.len(), SCOPE + SCOPE / 2 + 2);
+        assert_eq!(tree.get_first_key(), Some(&0));
+        assert_eq!(tree.get_last_key(), Some(&(SCOPE * 3)));
+        for (key, _) in tree.iter().skip(1).take(tree.len() - 2) {
+            let original = key - key % 3;
+            match key % 3 {
+                0 => assert_eq!(original % 2, 1),
+                1 => {}
+                _ => panic!("unexpected key {key}"),
+            }
+        }
+    }
+
+    #[test]
+    fn usize_cursor_forget() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+
+        // a leaked cursor takes the entries with it, but leaves a consistent, usable map
+        let mut cursor = tree.cursor_front_mut();
+        cursor.seek_index(SCOPE / 2);
+        std::mem::forget(cursor);
+        assert_eq!(tree.len(), 0);
+        assert_eq!(tree.iter().len(), 0);
+        assert_eq!(tree.iter().next(), None);
+        assert_eq!(tree.get(&3), None);
+        assert_eq!(tree.get_from_index(3), None);
+
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+        assert_eq!(tree.len(), SCOPE);
+        assert!(tree.keys().copied().eq(0..SCOPE));
+
+        // so does a leaked extract_if
+        let mut extract = tree.extract_if(|key, _| key % 2 == 0);
+        assert_eq!(extract.next(), Some((0, 0)));
+        std::mem::forget(extract);
+        assert!(tree.is_empty());
+        tree.insert(1, 1);
+        assert_eq!(tree.get_first_key(), Some(&1));
+    }
+
+    #[test]
+    fn usize_eq_ord_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash_of = |tree: &IndexTreeMap<usize, usize>| {
+            let mut hasher = DefaultHasher::new();
+            tree.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let mut ascending = IndexTreeMap::new();
+        let mut descending = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            ascending.insert(i, i);
+            descending.insert(SCOPE - 1 - i, SCOPE - 1 - i);
+        }
+        let mut shrunk = ascending.clone();
+        for i in SCOPE..SCOPE * 2 {
+            shrunk.insert(i, i);
+        }
+        while shrunk.len() > SCOPE {
+            shrunk.pop_last();
+        }
+
+        assert_eq!(ascending, descending);
+        assert_eq!(ascending, shrunk);
+        assert_eq!(hash_of(&ascending), hash_of(&descending));
+        assert_eq!(hash_of(&ascending), hash_of(&shrunk));
+        assert_eq!(ascending.cmp(&descending), std::cmp::Ordering::Equal);
+
+        descending.replace(&10, 0);
+        assert_ne!(ascending, descending);
+        assert!(descending < ascending);
+        shrunk.pop_last();
+        assert!(shrunk < ascending);
+        assert!(IndexTreeMap::<usize, usize>::new() < shrunk);
+    }
+
+    #[test]
+    fn string_index_operator() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i.to_string(), i)
+        }
+
+        for (index, (key, value)) in tree.clone().iter().enumerate() {
+            assert_eq!(tree[key.as_str()], *value);
+            assert_eq!(tree[key], *value);
+            assert_eq!(tree[Pos(index)], *value);
+            tree[Pos(index)] += 1;
+            assert_eq!(tree[key], value + 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "key not found")]
+    fn string_index_operator_missing_key() {
+        let mut tree = IndexTreeMap::new();
+        tree.insert("a".to_string(), 1);
+        let _ = tree["b"];
+    }
+
+    #[test]
+    #[should_panic(expected = "position out of bounds")]
+    fn usize_index_operator_out_of_bounds() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i)
+        }
+        tree[Pos(SCOPE)] = 0;
+    }
+
+    #[test]
+    fn usize_from_iter_extend() {
+        let shuffled = (0..SCOPE).map(|i| (i * 7919) % SCOPE);
+        let tree: IndexTreeMap<usize, usize> = shuffled.clone().map(|i| (i, i)).collect();
+        let sorted: IndexTreeMap<usize, usize> = (0..SCOPE).map(|i| (i, i)).collect();
+        assert_eq!(tree, sorted);
+        for i in 0..SCOPE {
+            assert_eq!(tree.get_key_value_from_index(i), Some((&i, &i)));
+        }
+
+        // duplicate keys take the value of their last occurrence
+        let tree: IndexTreeMap<usize, usize> = (0..SCOPE * 2).map(|i| (i % SCOPE, i)).collect();
+        assert_eq!(tree.len(), SCOPE);
+        assert!(tree.iter().all(|(k, v)| *v == k + SCOPE));
+
+        let mut extended = IndexTreeMap::new();
+        extended.extend((0..SCOPE / 2).map(|i| (i, i)));
+        extended.extend(sorted.iter().skip(SCOPE / 4));
+        assert_eq!(extended, sorted);
+
+        let from_array = IndexTreeMap::from([(3, "c"), (1, "a"), (2, "b"), (1, "z")]);
+        assert_eq!(
+            from_array.into_iter().collect::<Vec<_>>(),
+            vec![(1, "z"), (2, "b"), (3, "c")]
+        );
+    }
+
+    #[test]
+    fn usize_from_sorted_iter() {
+        let mut tree = IndexTreeMap::from_sorted_iter((0..SCOPE).map(|i| (i, i * 2)));
+        let mut inserted = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            inserted.insert(i, i * 2);
+        }
+        assert_eq!(tree, inserted);
+        for i in 0..SCOPE {
+            assert_eq!(tree.get(&i), Some(&(i * 2)));
+            assert_eq!(tree.get_index_from_key(&i), Some(i));
+        }
+        for i in (0..SCOPE).step_by(3) {
+            assert_eq!(tree.remove(&i), Some((i, i * 2)));
+        }
+        assert!(tree.keys().copied().eq((0..SCOPE).filter(|i| i % 3 != 0)));
+
+        let checked = IndexTreeMap::try_from_sorted_iter((0..SCOPE).map(|i| (i, i))).unwrap();
+        assert_eq!(checked.len(), SCOPE);
+        let unsorted = (0..SCOPE).map(|i| (i, i)).chain([(SCOPE / 2, 0)]);
+        let error = IndexTreeMap::try_from_sorted_iter(unsorted).unwrap_err();
+        assert_eq!(error.index, SCOPE);
+        let duplicate = [(1, 1), (2, 2), (2, 3), (4, 4)];
+        let error = IndexTreeMap::try_from_sorted_iter(duplicate).unwrap_err();
+        assert_eq!(error.index, 2);
+    }
+
+    #[test]
+    fn usize_non_clone_values() {
+        // a value type without Clone, like a file handle or a trait object
+        let boxed = |i: usize| -> Box<dyn Fn() -> usize> { Box::new(move || i) };
+
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, boxed(i));
+        }
+        for i in 0..SCOPE {
+            let old = tree.replace(&i, boxed(i * 2)).unwrap();
+            assert_eq!(old(), i);
+        }
+        let (key, value) = tree.remove(&10).unwrap();
+        assert_eq!((key, value()), (10, 20));
+        let (key, value) = tree.remove_from_index(0).unwrap();
+        assert_eq!((key, value()), (0, 0));
+        *tree.entry(10).or_insert(boxed(7)) = boxed(30);
+        assert_eq!(tree.get(&10).unwrap()(), 30);
+
+        let upper = tree.split_off(&(SCOPE / 2));
+        assert_eq!(tree.len() + upper.len(), SCOPE - 1);
+        assert!(upper.iter().all(|(k, v)| v() == k * 2));
+
+        let collected: IndexTreeMap<usize, Box<dyn Fn() -> usize>> =
+            (0..SCOPE).rev().map(|i| (i, boxed(i))).collect();
+        assert!(collected.iter().all(|(k, v)| v() == *k));
+    }
+
+    #[test]
+    fn string_replace_index() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i.to_string());
+        }
+        for i in 0..SCOPE {
+            assert_eq!(
+                tree.replace_index(i, (i * 2).to_string()),
+                Some(i.to_string())
+            );
+        }
+        assert_eq!(tree.replace_index(SCOPE, String::new()), None);
+        assert_eq!(tree.len(), SCOPE);
+        for i in 0..SCOPE {
+            assert_eq!(tree.get(&i), Some(&(i * 2).to_string()));
+        }
+    }
+
+    #[test]
+    fn usize_remove_drain_range() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i, i);
+        }
+
+        tree.remove_range(..SCOPE / 10);
+        assert_eq!(tree.len(), SCOPE - SCOPE / 10);
+        assert_eq!(
+            tree.get_key_value_from_index(0),
+            Some((&(SCOPE / 10), &(SCOPE / 10)))
+        );
+
+        let drained: Vec<_> = tree.drain_range(100..200).map(|(k, _)| k).collect();
+        assert!(drained.into_iter().eq(SCOPE / 10 + 100..SCOPE / 10 + 200));
+
+        tree.remove_key_range(SCOPE / 2..SCOPE / 2 + 50);
+        let drained: Vec<_> = tree.drain_key_range(SCOPE - 10..).map(|(k, _)| k).collect();
+        assert!(drained.into_iter().eq(SCOPE - 10..SCOPE));
+
+        let expected = (SCOPE / 10..SCOPE - 10)
+            .filter(|i| !(SCOPE / 10 + 100..SCOPE / 10 + 200).contains(i))
+            .filter(|i| !(SCOPE / 2..SCOPE / 2 + 50).contains(i));
+        assert!(tree.keys().copied().eq(expected.clone()));
+        for (index, key) in expected.enumerate() {
+            assert_eq!(tree.get_index_from_key(&key), Some(index));
+        }
+
+        // the map stays usable after its borders were cut and joined
+        for i in 0..SCOPE {
+            tree.insert(i, i);
+        }
+        for i in 0..SCOPE {
+            assert_eq!(tree.remove_from_index(0), Some((i, i)));
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn usize_insert_full() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            let key = (i * 7919) % SCOPE;
+            let (index, old) = tree.insert_full(key, i);
+            assert_eq!(old, None);
+            assert_eq!(tree.get_index_from_key(&key), Some(index));
+        }
+        for key in 0..SCOPE {
+            let (index, old) = tree.insert_full(key, key);
+            assert_eq!(index, key);
+            assert_eq!(
+                old,
+                Some((0..SCOPE).find(|i| (i * 7919) % SCOPE == key).unwrap())
+            );
+        }
+        assert_eq!(tree.len(), SCOPE);
+    }
+
+    #[test]
+    fn usize_try_insert() {
+        let mut tree = IndexTreeMap::new();
+        for i in (0..SCOPE).rev() {
+            let (index, value) = tree.try_insert(i, i).unwrap();
+            assert_eq!(index, 0);
+            *value += 1;
+        }
+        for i in 0..SCOPE {
+            let err = tree.try_insert(i, 0).unwrap_err();
+            assert_eq!(err.entry.index(), i);
+            assert_eq!(err.entry.get(), &(i + 1));
+            assert_eq!(err.value, 0);
+        }
+        assert_eq!(tree.len(), SCOPE);
+        assert!(tree.iter().all(|(k, v)| *v == k + 1));
+
+        let message = tree.try_insert(3, 0).unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "failed to insert 0, key 3 already exists at index 3 with value 4"
+        );
+    }
+
+    #[test]
+    fn usize_nth_from_key() {
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2, i);
+        }
+        for i in 0..SCOPE {
+            assert_eq!(tree.nth_from_key(&(i * 2), 0), Some((i, &(i * 2), &i)));
+            // odd keys are absent, so they anchor between two entries
+            assert_eq!(tree.nth_from_key(&(i * 2 + 1), 0), None);
+            for offset in [1, 7, 100] {
+                let expected = (i + offset < SCOPE).then(|| i + offset);
+                let present = tree.nth_from_key(&(i * 2), offset as isize);
+                assert_eq!(present.map(|(index, _, _)| index), expected);
+                let absent = tree.nth_from_key(&(i * 2 + 1), offset as isize);
+                assert_eq!(absent.map(|(index, _, _)| index), expected);
+                let expected = i.checked_sub(offset);
+                let present = tree.nth_from_key(&(i * 2), -(offset as isize));
+                assert_eq!(present.map(|(index, _, _)| index), expected);
+                let absent = tree.nth_from_key(&(i * 2 + 1), -(offset as isize));
+                assert_eq!(
+                    absent.map(|(index, _, _)| index),
+                    (i + 1).checked_sub(offset)
+                );
+            }
+        }
+        assert_eq!(tree.nth_from_key(&0, isize::MAX), None);
+        assert_eq!(tree.nth_from_key(&0, isize::MIN), None);
+
+        // paginating from a deleted anchor continues from where it was
+        tree.remove(&100);
+        assert_eq!(tree.nth_from_key(&100, 1), Some((50, &102, &51)));
+        assert_eq!(tree.nth_from_key(&100, -1), Some((49, &98, &49)));
+    }
+
+    #[test]
+    fn usize_count_range() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        let mut tree = IndexTreeMap::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 3, i);
+        }
+        let bound = |kind: usize, key: usize| match kind {
+            0 => Included(key),
+            1 => Excluded(key),
+            _ => Unbounded,
+        };
+        for start in (0..SCOPE * 3).step_by(397) {
+            for end in (start + 1..SCOPE * 3 + 2).step_by(401) {
+                for kinds in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (0, 2), (2, 2)] {
+                    let range = (bound(kinds.0, start), bound(kinds.1, end));
+                    assert_eq!(tree.count_range(range), tree.range(range).count());
+                }
+            }
+        }
+        assert_eq!(tree.count_range(..), SCOPE);
+        assert_eq!(tree.count_range(SCOPE * 3..), 0);
+    }
+
     //* BYTE ARRAY TESTS *//
     // * * Expansive Testing has a time complexity of O(SCOPE * SCOPE)
     #[test]
@@ -1,8 +1,9 @@
 #[cfg(test)]
 pub mod tests {
 
-    use indextreemap::IndexTreeSet;
+    use indextreemap::{IndexTreeSet, Pos};
     use sha2::{Digest, Sha256};
+    use std::ops::Bound::{Excluded, Included};
 
     const SCOPE: usize = 2_000;
 
@@ -26,7 +27,7 @@ pub mod tests {
     fn clear_test() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(hash(i.to_le_bytes().as_slice()))
+            tree.insert(hash(i.to_le_bytes().as_slice()));
         }
 
         for i in 0..SCOPE {
@@ -42,7 +43,7 @@ pub mod tests {
         }
 
         for i in 0..SCOPE {
-            tree.insert(hash(i.to_le_bytes().as_slice()))
+            tree.insert(hash(i.to_le_bytes().as_slice()));
         }
 
         for i in 0..SCOPE {
@@ -58,7 +59,7 @@ pub mod tests {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
             for j in 0..i {
-                tree.insert(hash(j.to_le_bytes().as_slice()))
+                tree.insert(hash(j.to_le_bytes().as_slice()));
             }
 
             // println!("{tree:#?}");
@@ -74,7 +75,7 @@ pub mod tests {
     fn string_index_from_key() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(hash(i.to_le_bytes().as_slice()))
+            tree.insert(hash(i.to_le_bytes().as_slice()));
         }
 
         // println!("{tree:#?}");
@@ -91,7 +92,7 @@ pub mod tests {
     fn string_remove() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(hash(i.to_le_bytes().as_slice()))
+            tree.insert(hash(i.to_le_bytes().as_slice()));
         }
 
         let removed = SCOPE / 3 + 1;
@@ -131,7 +132,7 @@ pub mod tests {
         for a in 10..SCOPE {
             let mut tree = IndexTreeSet::new();
             for i in 0..a {
-                tree.insert(hash(i.to_le_bytes().as_slice()))
+                tree.insert(hash(i.to_le_bytes().as_slice()));
             }
 
             // println!("{tree:#?}");
@@ -156,7 +157,7 @@ pub mod tests {
         for a in 1..SCOPE {
             let mut tree = IndexTreeSet::new();
             for i in 0..a {
-                tree.insert(hash(i.to_le_bytes().as_slice()))
+                tree.insert(hash(i.to_le_bytes().as_slice()));
             }
 
             // println!("{tree:#?}");
@@ -190,7 +191,7 @@ pub mod tests {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
             for j in 0..i {
-                tree.insert(j as i32)
+                tree.insert(j as i32);
             }
 
             // println!("{tree:#?}");
@@ -206,7 +207,7 @@ pub mod tests {
     fn i32_remove() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(i as i32)
+            tree.insert(i as i32);
         }
 
         let removed = SCOPE / 3 + 1;
@@ -222,7 +223,7 @@ pub mod tests {
         for a in 10..SCOPE {
             let mut tree = IndexTreeSet::new();
             for i in 0..a {
-                tree.insert(i as i32)
+                tree.insert(i as i32);
             }
 
             // println!("{tree:#?}");
@@ -249,7 +250,7 @@ pub mod tests {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
             for j in 0..i {
-                tree.insert(j)
+                tree.insert(j);
             }
 
             // println!("{tree:#?}");
@@ -265,7 +266,7 @@ pub mod tests {
     fn usize_index_from_key() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(i)
+            tree.insert(i);
         }
 
         // println!("{tree:#?}");
@@ -279,7 +280,7 @@ pub mod tests {
     fn usize_remove() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(i)
+            tree.insert(i);
         }
 
         let removed = SCOPE / 3 + 1;
@@ -295,7 +296,7 @@ pub mod tests {
         for a in 10..SCOPE {
             let mut tree = IndexTreeSet::new();
             for i in 0..a {
-                tree.insert(i)
+                tree.insert(i);
             }
 
             // println!("{tree:#?}");
@@ -322,7 +323,7 @@ pub mod tests {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
             for j in 0..i {
-                tree.insert(j.to_le_bytes())
+                tree.insert(j.to_le_bytes());
             }
 
             // println!("{tree:#?}");
@@ -338,7 +339,7 @@ pub mod tests {
     fn byte_array_index_from_key() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(i.to_le_bytes())
+            tree.insert(i.to_le_bytes());
         }
 
         // println!("{tree:#?}");
@@ -353,7 +354,7 @@ pub mod tests {
     fn byte_array_remove() {
         let mut tree = IndexTreeSet::new();
         for i in 0..SCOPE {
-            tree.insert(i.to_le_bytes())
+            tree.insert(i.to_le_bytes());
         }
 
         let removed = SCOPE / 3 + 1;
@@ -369,7 +370,7 @@ pub mod tests {
         for a in 10..SCOPE {
             let mut tree: IndexTreeSet<[u8; 8]> = IndexTreeSet::new();
             for i in 0..a {
-                tree.insert(i.to_le_bytes())
+                tree.insert(i.to_le_bytes());
             }
 
             // println!("{tree:#?}");
@@ -388,4 +389,354 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn string_range() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(hash(i.to_le_bytes().as_slice()));
+        }
+
+        let mut keys: Vec<String> = (0..SCOPE)
+            .map(|i| hash(i.to_le_bytes().as_slice()))
+            .collect();
+        keys.sort();
+
+        let start = &keys[SCOPE / 4];
+        let end = &keys[SCOPE / 2];
+        let range = tree.range(start.clone()..end.clone());
+        assert_eq!(range.range.start, SCOPE / 4);
+        assert_eq!(
+            range.collect::<Vec<_>>(),
+            keys[SCOPE / 4..SCOPE / 2].iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn usize_slice() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 3);
+        }
+
+        let page = tree.slice(SCOPE / 2..);
+        assert_eq!(page.len(), SCOPE - SCOPE / 2);
+        assert_eq!(page.get(0), Some(&(SCOPE / 2 * 3)));
+        assert_eq!(
+            page.slice(1..3).iter().collect::<Vec<_>>(),
+            vec![&(SCOPE / 2 * 3 + 3), &(SCOPE / 2 * 3 + 6)]
+        );
+        assert_eq!(tree.range_by_index(..2).collect::<Vec<_>>(), vec![&0, &3]);
+    }
+
+    #[test]
+    fn string_borrowed_lookups() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(i.to_string());
+        }
+
+        assert!(tree.contains_key("10"));
+        assert_eq!(tree.get("10"), Some(&"10".to_string()));
+        assert_eq!(tree.get_index_from_key("0"), Some(0));
+        assert_eq!(
+            tree.range::<str, _>((Included("1"), Excluded("2"))).count(),
+            1 + 10 + 100 + 1000
+        );
+        assert_eq!(tree.remove("10"), Some("10".to_string()));
+        assert!(!tree.contains_key("10"));
+
+        let split_tree = tree.split_off("5");
+        assert!(split_tree.contains_key("5"));
+        assert!(!tree.contains_key("5"));
+    }
+
+    #[test]
+    fn string_rank() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(hash(i.to_le_bytes().as_slice()));
+        }
+
+        for (index, key) in tree.iter().enumerate() {
+            assert_eq!(tree.rank(key), Ok(index));
+            assert_eq!(tree.lower_bound_index(key), index);
+            assert_eq!(tree.upper_bound_index(key), index + 1);
+
+            let mut absent = key.clone();
+            absent.push('!');
+            assert_eq!(tree.rank(&absent), Err(index + 1));
+        }
+    }
+
+    #[test]
+    fn i32_floor_ceiling() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE as i32 {
+            tree.insert(i * 3);
+        }
+
+        assert_eq!(tree.floor(&-1), None);
+        assert_eq!(tree.floor(&4), Some((1, &3)));
+        assert_eq!(tree.ceiling(&4), Some((2, &6)));
+        assert_eq!(tree.predecessor(&6), Some((1, &3)));
+        assert_eq!(tree.successor(&6), Some((3, &9)));
+        assert_eq!(tree.ceiling(&(SCOPE as i32 * 3)), None);
+        assert_eq!(
+            tree.floor(&(SCOPE as i32 * 3)),
+            Some((SCOPE - 1, &(SCOPE as i32 * 3 - 3)))
+        );
+    }
+
+    #[test]
+    fn string_pop_first_last() {
+        let mut tree = IndexTreeSet::new();
+        let mut sorted = Vec::new();
+        for i in 0..SCOPE {
+            let key = hash(i.to_le_bytes().as_slice());
+            tree.insert(key.clone());
+            sorted.push(key);
+        }
+        sorted.sort();
+
+        let mut front = sorted.iter();
+        let mut back = sorted.iter().rev();
+        while !tree.is_empty() {
+            assert_eq!(tree.pop_first().as_ref(), front.next());
+            assert_eq!(tree.pop_last().as_ref(), back.next());
+        }
+        assert_eq!(tree.pop_first(), None);
+    }
+
+    #[test]
+    fn i32_retain() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE as i32 {
+            tree.insert(i - SCOPE as i32 / 2);
+        }
+
+        tree.retain(|key| *key >= 0);
+        assert_eq!(tree.len(), SCOPE - SCOPE / 2);
+        assert_eq!(tree.get_first(), Some(&0));
+        assert_eq!(tree.get_index_from_key(&10), Some(10));
+    }
+
+    #[test]
+    fn string_into_iter() {
+        let mut tree = IndexTreeSet::new();
+        let mut sorted = Vec::new();
+        for i in 0..SCOPE {
+            let key = hash(i.to_le_bytes().as_slice());
+            tree.insert(key.clone());
+            sorted.push(key);
+        }
+        sorted.sort();
+
+        assert!((&tree).into_iter().eq(sorted.iter()));
+        assert_eq!(tree.into_iter().collect::<Vec<String>>(), sorted);
+    }
+
+    #[test]
+    fn i32_iter_double_ended() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE as i32 {
+            tree.insert(-i);
+        }
+
+        let mut iter = tree.iter();
+        assert_eq!(iter.len(), SCOPE);
+        assert_eq!(iter.next_back(), Some(&0));
+        assert_eq!(iter.nth_back(SCOPE - 3), Some(&-(SCOPE as i32 - 2)));
+        assert_eq!(iter.next(), Some(&-(SCOPE as i32 - 1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        assert!(tree
+            .clone()
+            .into_iter()
+            .rev()
+            .eq((-(SCOPE as i32 - 1)..=0).rev()));
+        assert!(tree
+            .range(-10..)
+            .rev()
+            .eq([0, -1, -2, -3, -4, -5, -6, -7, -8, -9, -10].iter()));
+    }
+
+    #[test]
+    fn string_iter_from() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(hash(i.to_le_bytes().as_slice()));
+        }
+        let sorted: Vec<&String> = tree.iter().collect();
+
+        let resume = sorted[SCOPE / 3];
+        assert!(tree
+            .iter_from_key(resume)
+            .eq(sorted[SCOPE / 3..].iter().copied()));
+        assert!(tree
+            .iter_from_key_rev(resume.as_str())
+            .eq(sorted[..=SCOPE / 3].iter().rev().copied()));
+        assert!(tree.iter_from_index(10).eq(sorted[10..].iter().copied()));
+        assert!(tree
+            .iter_from_index_rev(10)
+            .eq(sorted[..=10].iter().rev().copied()));
+    }
+
+    #[test]
+    fn usize_sets_as_keys() {
+        use std::collections::{BTreeMap, HashSet};
+
+        let mut forward = IndexTreeSet::new();
+        let mut backward = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            forward.insert(i);
+            backward.insert(SCOPE - 1 - i);
+        }
+
+        let mut hashed = HashSet::new();
+        hashed.insert(forward.clone());
+        assert!(hashed.contains(&backward));
+
+        let mut ordered = BTreeMap::new();
+        ordered.insert(forward.clone(), "all");
+        forward.pop_last();
+        ordered.insert(forward, "all but last");
+        assert_eq!(ordered.get(&backward), Some(&"all"));
+        assert_eq!(ordered.values().next(), Some(&"all but last"));
+    }
+
+    #[test]
+    fn usize_index_operator() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(i * 2);
+        }
+        for i in 0..SCOPE {
+            assert_eq!(tree[Pos(i)], i * 2);
+        }
+    }
+
+    #[test]
+    fn i32_from_iter_extend() {
+        let scope = SCOPE as i32;
+        let tree: IndexTreeSet<i32> = (0..scope).rev().chain(0..scope).collect();
+        assert_eq!(tree.len(), SCOPE);
+        assert!(tree.iter().copied().eq(0..scope));
+
+        let mut extended = IndexTreeSet::from([scope, -1, scope]);
+        extended.extend(0..scope);
+        extended.extend(&[-2, scope + 1]);
+        assert_eq!(extended.len(), SCOPE + 4);
+        assert!(extended.iter().copied().eq(-2..scope + 2));
+    }
+
+    #[test]
+    fn string_from_sorted_iter() {
+        let mut keys: Vec<String> = (0..SCOPE).map(|i| i.to_string()).collect();
+        keys.sort();
+
+        let mut tree = IndexTreeSet::from_sorted_iter(keys.clone());
+        assert_eq!(tree.len(), SCOPE);
+        for (index, key) in keys.iter().enumerate() {
+            assert_eq!(tree.get_index_from_key(key), Some(index));
+        }
+        for key in keys.iter().step_by(2) {
+            assert!(tree.remove(key).is_some());
+        }
+        assert!(tree.iter().eq(keys.iter().skip(1).step_by(2)));
+
+        assert!(IndexTreeSet::try_from_sorted_iter(keys.clone()).is_ok());
+        keys.swap(10, 11);
+        let error = IndexTreeSet::try_from_sorted_iter(keys).unwrap_err();
+        assert_eq!(error.index, 11);
+    }
+
+    #[test]
+    fn usize_non_clone_keys() {
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+        struct Handle(usize);
+
+        let mut tree = IndexTreeSet::new();
+        for i in (0..SCOPE).rev() {
+            tree.insert(Handle(i));
+        }
+        assert_eq!(tree.remove(&Handle(5)), Some(Handle(5)));
+        assert_eq!(tree.remove_from_index(0), Some(Handle(0)));
+        let upper = tree.split_off(&Handle(SCOPE / 2));
+        assert_eq!(upper.get_first(), Some(&Handle(SCOPE / 2)));
+        assert_eq!(tree.len() + upper.len(), SCOPE - 2);
+    }
+
+    #[test]
+    fn i32_remove_drain_range() {
+        let scope = SCOPE as i32;
+        let mut tree = IndexTreeSet::from_sorted_iter(0..scope);
+
+        let drained: Vec<_> = tree.drain_range(10..20).collect();
+        assert!(drained.into_iter().eq(10..20));
+        tree.remove_key_range(..=5);
+        tree.remove_range(tree.len() - 5..);
+        let drained: Vec<_> = tree.drain_key_range(100..200).collect();
+        assert!(drained.into_iter().eq(100..200));
+
+        let expected: Vec<_> = (6..scope - 5)
+            .filter(|i| !(10..20).contains(i) && !(100..200).contains(i))
+            .collect();
+        assert!(tree.iter().eq(expected.iter()));
+        for key in expected {
+            assert!(tree.remove(&key).is_some());
+        }
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn i32_insert_full() {
+        let scope = SCOPE as i32;
+        let mut tree = IndexTreeSet::new();
+        for i in (0..scope).rev() {
+            assert!(tree.insert(i * 2));
+        }
+        for i in 0..scope {
+            assert!(!tree.insert(i * 2));
+            assert_eq!(tree.insert_full(i * 2), (i as usize * 2, false));
+            assert_eq!(tree.insert_full(i * 2 + 1), (i as usize * 2 + 1, true));
+        }
+        assert_eq!(tree.len(), SCOPE * 2);
+    }
+
+    #[test]
+    fn string_nth_from_key() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(format!("{i:05}"));
+        }
+        for i in 0..SCOPE - 10 {
+            let anchor = format!("{i:05}");
+            let expected = format!("{:05}", i + 10);
+            assert_eq!(tree.nth_from_key(&anchor, 10), Some((i + 10, &expected)));
+            let between = format!("{i:05}a");
+            assert_eq!(tree.nth_from_key(&between, 10), Some((i + 10, &expected)));
+            assert_eq!(tree.nth_from_key(&expected, -10), Some((i, &anchor)));
+        }
+        assert_eq!(tree.nth_from_key("", 0), None);
+        assert_eq!(tree.nth_from_key("", 1), Some((0, &format!("{:05}", 0))));
+    }
+
+    #[test]
+    fn string_count_range() {
+        let mut tree = IndexTreeSet::new();
+        for i in 0..SCOPE {
+            tree.insert(format!("{i:05}"));
+        }
+        assert_eq!(tree.count_range::<str, _>(..), SCOPE);
+        for i in (0..SCOPE).step_by(13) {
+            let key = format!("{i:05}");
+            assert_eq!(tree.count_range::<String, _>(..&key), i);
+            assert_eq!(tree.count_range::<String, _>(&key..), SCOPE - i);
+            let upper = format!("{:05}a", i + 100);
+            let expected = (i + 101).min(SCOPE) - i;
+            assert_eq!(tree.count_range::<String, _>(&key..=&upper), expected);
+        }
+    }
 }